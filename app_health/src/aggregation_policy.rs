@@ -0,0 +1,27 @@
+use crate::{Health, Report};
+use core::fmt::Debug;
+
+/// Determines the overall health of an application from the health of its components.
+///
+/// An aggregator invokes its policy whenever it needs to recompute the application's overall health. The policy
+/// receives a summary of every live component, which includes the component's name, its health state, and the number
+/// of publisher signals in each health state. The summaries don't include any signals.
+///
+/// Policies should generally use [`Report::effective_state`] rather than [`Report::state`], so that each
+/// component's [`Criticality`](crate::Criticality) is honored.
+///
+/// The crate provides a few built-in policies:
+///
+/// - [`WorstOf`]: The overall health is the most severe health of any component. This is the default.
+/// - [`Quorum`]: The overall health is the best health shared by a minimum number of components.
+/// - [`Weighted`]: The overall health is the most severe health reached by a given fraction of the total component weight.
+///
+/// Policies are configured with [`AggregatorBuilder::policy`](crate::AggregatorBuilder::policy).
+///
+/// [`WorstOf`]: crate::WorstOf
+/// [`Quorum`]: crate::Quorum
+/// [`Weighted`]: crate::Weighted
+pub trait AggregationPolicy: Debug + Send + Sync {
+    /// Compute the overall health given a summary of each component.
+    fn aggregate(&self, components: &[Report]) -> Health;
+}
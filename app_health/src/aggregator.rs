@@ -1,43 +1,161 @@
-use crate::component::Component;
 use crate::component_monitor::ComponentMonitor;
+use crate::component_transition::ComponentTransition;
 use crate::debouncer::Debouncer;
-use crate::{Filter, Health, Reports};
+use crate::dependencies::Dependencies;
+use crate::drain_guard::DrainGuard;
+use crate::runtime::{default_runtime, drive, drive_stream};
+use crate::sync::{broadcast, mpsc, oneshot, watch};
+use crate::transition::transitions;
+use crate::{
+    AggregationPolicy, AggregatorBuilder, Closed, Component, ComponentBuilder, DependencyCycle, DependencyGraph, Filter, Health,
+    HealthOverride, Maintenance, ProbeStatus, Probes, Report, Reports, Runtime, Transition,
+};
+use core::convert::Infallible;
+use core::future::pending;
+use core::mem::replace;
 use core::time::Duration;
-use tokio::sync::{mpsc, oneshot, watch};
+use futures_util::{FutureExt, Stream, select};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
 /// Aggregates health state from multiple components.
 #[derive(Debug)]
+#[expect(clippy::struct_field_names, reason = "field names are clear and unambiguous")]
 pub struct Aggregator {
     aggregator_tx: mpsc::UnboundedSender<AggregatorMessage>,
     health_rx: watch::Receiver<Health>,
+    liveness_rx: watch::Receiver<ProbeStatus>,
+    readiness_rx: watch::Receiver<ProbeStatus>,
+    shutdown_rx: watch::Receiver<Shutdown>,
+    drain_tx: mpsc::WeakUnboundedSender<Infallible>,
+    #[cfg_attr(not(feature = "otel"), expect(dead_code, reason = "only used by some features"))]
+    transitions_tx: broadcast::Sender<ComponentTransition>,
+    component_debounce_interval: Duration,
+    policy: Arc<dyn AggregationPolicy>,
+    runtime: Arc<dyn Runtime>,
 }
 
 /// Messages sent to the aggregator worker.
 pub enum AggregatorMessage {
     ComponentCreated(ComponentMonitor),
     ComponentDropped,
-    ComponentHealthChanged,
+    ComponentHealthChanged(Option<Box<ComponentTransition>>),
     GetReport(Filter, oneshot::Sender<Reports>),
+    #[cfg_attr(not(feature = "grpc"), expect(dead_code, reason = "only used by some features"))]
+    FindComponent(Box<str>, oneshot::Sender<Option<watch::Receiver<Report>>>),
+    AddDependency(Arc<str>, Arc<str>, oneshot::Sender<Result<(), DependencyCycle>>),
+    GetDependencyGraph(oneshot::Sender<DependencyGraph>),
+    SetMaintenance(Arc<str>, Option<Maintenance>, oneshot::Sender<bool>),
+    SetOverride(Option<HealthOverride>),
+    BeginShutdown(Duration),
 }
 
-// we only send state updates at most once per second (TODO: should come from config)
-const MIN_DEBOUNCE_INTERVAL: Duration = Duration::from_secs(1);
+// how many component transitions can be buffered for each subscriber before the oldest ones are dropped
+const TRANSITION_CAPACITY: usize = 64;
 
 impl Aggregator {
-    /// Create a new health aggregator.
+    /// Create a new health aggregator with default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configured [`Runtime`](crate::Runtime) can't spawn the aggregator's worker, such as when using the
+    /// default [`TokioRuntime`](crate::TokioRuntime) from outside of a Tokio runtime.
     #[must_use]
     pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Create a builder to configure a new health aggregator.
+    #[must_use]
+    pub fn builder() -> AggregatorBuilder {
+        AggregatorBuilder::new()
+    }
+
+    pub(crate) fn from_builder(builder: &AggregatorBuilder) -> Self {
         let (aggregator_tx, aggregator_rx) = mpsc::unbounded_channel();
         let (health_tx, health_rx) = watch::channel(Health::Nominal);
+        let (liveness_tx, liveness_rx) = watch::channel(ProbeStatus::Pass);
+        let runtime = builder.runtime.clone().unwrap_or_else(default_runtime);
+        let startup_deadline = if builder.startup_grace_period.is_zero() {
+            None
+        } else {
+            runtime.now().checked_add(builder.startup_grace_period)
+        };
+
+        let initial_readiness = if startup_deadline.is_some() {
+            ProbeStatus::Starting
+        } else {
+            ProbeStatus::Pass
+        };
+
+        let (readiness_tx, readiness_rx) = watch::channel(initial_readiness);
+        let (shutdown_tx, shutdown_rx) = watch::channel(Shutdown::Running);
+        let (drain_tx, drain_rx) = mpsc::unbounded_channel();
+        let (transitions_tx, _) = broadcast::channel(TRANSITION_CAPACITY);
+
+        let views = Views {
+            policy: Arc::clone(&builder.policy),
+            health_tx,
+            liveness: ProbeView {
+                probe: Probes::LIVENESS,
+                threshold: builder.liveness_threshold,
+                startup_status: ProbeStatus::Pass,
+                status_tx: liveness_tx,
+            },
+            readiness: ProbeView {
+                probe: Probes::READINESS,
+                threshold: builder.readiness_threshold,
+                startup_status: ProbeStatus::Starting,
+                status_tx: readiness_tx,
+            },
+            health_override: None,
+            startup_deadline,
+            shutdown_tx,
+            shutdown_deadline: None,
+            drain_tx: Some(drain_tx.clone()),
+            runtime: Arc::clone(&runtime),
+        };
 
-        drop(tokio::spawn(aggregator_worker(aggregator_rx, health_tx, MIN_DEBOUNCE_INTERVAL)));
+        runtime.spawn(Box::pin(aggregator_worker(
+            aggregator_rx,
+            views,
+            Dependencies::new(builder.impact_threshold, builder.impact_state),
+            transitions_tx.clone(),
+            Debouncer::new(builder.debounce_interval, Arc::clone(&runtime)),
+            drain_rx,
+        )));
 
-        Self { aggregator_tx, health_rx }
+        Self {
+            aggregator_tx,
+            health_rx,
+            liveness_rx,
+            readiness_rx,
+            shutdown_rx,
+            drain_tx: drain_tx.downgrade(),
+            transitions_tx,
+            component_debounce_interval: builder.component_debounce_interval,
+            policy: Arc::clone(&builder.policy),
+            runtime,
+        }
     }
 
-    /// Create a new component.
+    /// Create a new component with default settings.
     pub fn component(&self, name: impl AsRef<str>) -> Component {
-        Component::new(name, self.aggregator_tx.downgrade())
+        self.component_builder(name).build()
+    }
+
+    /// Create a builder to configure a new component.
+    ///
+    /// The builder starts out with the component defaults configured on this aggregator.
+    pub fn component_builder(&self, name: impl AsRef<str>) -> ComponentBuilder {
+        ComponentBuilder::new(
+            name,
+            self.aggregator_tx.downgrade(),
+            self.component_debounce_interval,
+            Arc::clone(&self.policy),
+            DrainGuard::new(&self.drain_tx),
+            Arc::clone(&self.runtime),
+        )
     }
 
     /// Track changes to the application's health state over time.
@@ -49,18 +167,231 @@ impl Aggregator {
     /// # Errors
     ///
     /// The only reason why this future ever fails is when the aggregator has been dropped.
-    pub async fn changed(&mut self) -> Result<(), ()> {
-        self.health_rx.changed().await.map_err(|_e| ())
+    pub async fn changed(&mut self) -> Result<(), Closed> {
+        drive(&*self.runtime, self.health_rx.changed()).await.map_err(|_e| Closed)
+    }
+
+    /// Get a stream of the transitions of the application's overall health state from one state to another.
+    ///
+    /// Unlike [`changed`](Self::changed), each item carries both the old and the new state, so there's no need to query the
+    /// state separately. Like [`changed`](Self::changed), quick successions of changes are debounced, so intermediate
+    /// states may be skipped. The stream ends once the aggregator has been dropped.
+    pub fn watch(&self) -> impl Stream<Item = Transition> + Send + use<> {
+        drive_stream(Arc::clone(&self.runtime), transitions(self.health_rx.clone(), None, |state| *state))
     }
 
     /// Get the overall health state of the application.
     ///
-    /// The overall health is determined by the most severe health state reported by any component.
+    /// The overall health is determined by the aggregator's [`AggregationPolicy`]. By default, this is the
+    /// most severe health state reported by any component, capped according to each component's
+    /// [`Criticality`](crate::Criticality).
     #[must_use]
     pub fn state(&self) -> Health {
+        self.runtime.run_until_stalled();
         *self.health_rx.borrow()
     }
 
+    /// Get the outcome of the application's liveness probe.
+    ///
+    /// Liveness indicates whether the application should be left running or be restarted. The probe fails once the
+    /// health of the components that contribute to [`Probes::LIVENESS`] reaches the threshold configured with
+    /// [`AggregatorBuilder::liveness_threshold`].
+    ///
+    /// The probe passes during the application's startup grace period, and components that are still in their own grace
+    /// period don't count toward it, see [`AggregatorBuilder::startup_grace_period`].
+    #[must_use]
+    pub fn liveness(&self) -> ProbeStatus {
+        self.runtime.run_until_stalled();
+        *self.liveness_rx.borrow()
+    }
+
+    /// Track changes to the outcome of the application's liveness probe.
+    ///
+    /// This method's future will resolve when the outcome of the liveness probe has changed.
+    ///
+    /// # Errors
+    ///
+    /// The only reason why this future ever fails is when the aggregator has been dropped.
+    pub async fn liveness_changed(&mut self) -> Result<(), Closed> {
+        drive(&*self.runtime, self.liveness_rx.changed()).await.map_err(|_e| Closed)
+    }
+
+    /// Get the outcome of the application's readiness probe.
+    ///
+    /// Readiness indicates whether the application is able to serve traffic. The probe fails once the
+    /// health of the components that contribute to [`Probes::READINESS`] reaches the threshold configured with
+    /// [`AggregatorBuilder::readiness_threshold`].
+    ///
+    /// While the application or any of the components that contribute to the probe are in their startup grace period,
+    /// the probe reports [`ProbeStatus::Starting`] instead of passing, see [`AggregatorBuilder::startup_grace_period`].
+    #[must_use]
+    pub fn readiness(&self) -> ProbeStatus {
+        self.runtime.run_until_stalled();
+        *self.readiness_rx.borrow()
+    }
+
+    /// Track changes to the outcome of the application's readiness probe.
+    ///
+    /// This method's future will resolve when the outcome of the readiness probe has changed.
+    ///
+    /// # Errors
+    ///
+    /// The only reason why this future ever fails is when the aggregator has been dropped.
+    pub async fn readiness_changed(&mut self) -> Result<(), Closed> {
+        drive(&*self.runtime, self.readiness_rx.changed()).await.map_err(|_e| Closed)
+    }
+
+    /// Get a receiver that observes each change to the overall health state of the application.
+    #[cfg(any(feature = "grpc", feature = "otel"))]
+    pub(crate) fn subscribe(&self) -> watch::Receiver<Health> {
+        self.health_rx.clone()
+    }
+
+    /// Get a receiver for each transition of any component from one health state to another.
+    #[cfg(feature = "otel")]
+    pub(crate) fn transitions(&self) -> broadcast::Receiver<ComponentTransition> {
+        self.transitions_tx.subscribe()
+    }
+
+    /// Get a weak sender that can be used to query the aggregator worker without keeping it alive.
+    #[cfg(feature = "otel")]
+    pub(crate) fn downgrade(&self) -> mpsc::WeakUnboundedSender<AggregatorMessage> {
+        self.aggregator_tx.downgrade()
+    }
+
+    /// Get the runtime that runs the aggregator's workers.
+    #[cfg(any(feature = "grpc", feature = "otel"))]
+    pub(crate) fn runtime(&self) -> &Arc<dyn Runtime> {
+        &self.runtime
+    }
+
+    /// Returns `true` while the aggregator's worker is processing messages.
+    #[cfg(feature = "grpc")]
+    pub(crate) fn is_running(&self) -> bool {
+        !self.aggregator_tx.is_closed()
+    }
+
+    /// Find a live component by name, returning a receiver that observes each summary of its health.
+    ///
+    /// This returns `None` if there is no such component, or if the aggregator has been dropped.
+    #[cfg(feature = "grpc")]
+    pub(crate) async fn find_component(&self, name: &str) -> Option<watch::Receiver<Report>> {
+        self.request(|response_tx| AggregatorMessage::FindComponent(name.into(), response_tx))
+            .await?
+    }
+
+    /// Declare that one component depends on another.
+    ///
+    /// Components are identified by name, and need not exist yet. Once the dependency's health reaches the threshold
+    /// set with [`AggregatorBuilder::impact_threshold`], the dependent component is considered upstream impacted. It
+    /// then gets an extra signal in the state set with [`AggregatorBuilder::impact_state`], carrying an
+    /// `upstream_impacted` attribute and a `dependency` attribute naming the dependency. This happens even if the
+    /// dependent component's own publishers are `Nominal`, and the signal goes away once the dependency recovers.
+    ///
+    /// Only components created directly from the aggregator can take part in dependencies, not child components.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the dependency would create a cycle, in which case the dependency isn't added.
+    pub async fn add_dependency(&self, dependent: impl AsRef<str>, dependency: impl AsRef<str>) -> Result<(), DependencyCycle> {
+        let (dependent, dependency) = (dependent.as_ref().into(), dependency.as_ref().into());
+
+        // the worker only goes away along with the aggregator
+        self.request(|response_tx| AggregatorMessage::AddDependency(dependent, dependency, response_tx))
+            .await
+            .unwrap_or(Ok(()))
+    }
+
+    /// Get a snapshot of the dependencies between components, along with the health of each component.
+    ///
+    /// The snapshot can be rendered as a Mermaid diagram with [`DependencyGraph::to_mermaid`].
+    ///
+    /// This returns `None` if the aggregator has been dropped.
+    #[must_use]
+    pub async fn dependency_graph(&self) -> Option<DependencyGraph> {
+        self.request(AggregatorMessage::GetDependencyGraph).await
+    }
+
+    /// Put the components with the given name into maintenance, limiting their impact on the application's overall health.
+    ///
+    /// This is the same as [`Component::begin_maintenance`], but doesn't need access to the component itself, which makes
+    /// it suitable for admin APIs that put components into maintenance at runtime. Only components created directly from
+    /// the aggregator can be found by name, not child components.
+    ///
+    /// Returns `false` if there is no live component with the given name.
+    pub async fn begin_maintenance(&self, component: impl AsRef<str>, maintenance: Maintenance) -> bool {
+        self.set_maintenance(component.as_ref(), Some(maintenance)).await
+    }
+
+    /// Take the components with the given name out of maintenance ahead of time.
+    ///
+    /// Returns `false` if there is no live component with the given name.
+    pub async fn end_maintenance(&self, component: impl AsRef<str>) -> bool {
+        self.set_maintenance(component.as_ref(), None).await
+    }
+
+    async fn set_maintenance(&self, component: &str, maintenance: Option<Maintenance>) -> bool {
+        self.request(|response_tx| AggregatorMessage::SetMaintenance(component.into(), maintenance, response_tx))
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Force the overall health of the application, regardless of the health of its components.
+    ///
+    /// The forced state is what [`state`](Self::state) returns, and it determines the outcome of the liveness and
+    /// readiness probes according to their thresholds. With the default thresholds, forcing
+    /// [`Critical`](Health::Critical) fails readiness to drain the application from a load balancer while keeping it
+    /// alive, and forcing [`Down`](Health::Down) fails both probes.
+    ///
+    /// The override replaces any override already in effect, and ends on its own once its duration has passed. It's
+    /// included in [`reports`](Self::reports), see [`Reports::health_override`]. Components keep tracking their own
+    /// health as usual in the meantime.
+    pub fn override_health(&self, health_override: HealthOverride) {
+        let _ = self.aggregator_tx.send(AggregatorMessage::SetOverride(Some(health_override)));
+        self.runtime.run_until_stalled();
+    }
+
+    /// Remove the override of the application's overall health ahead of time.
+    pub fn clear_override(&self) {
+        let _ = self.aggregator_tx.send(AggregatorMessage::SetOverride(None));
+        self.runtime.run_until_stalled();
+    }
+
+    /// Begin shutting down the application, typically upon receiving `SIGTERM`.
+    ///
+    /// The readiness probe fails right away so the application stops receiving traffic, while the liveness probe keeps
+    /// passing so the application isn't restarted as its components wind down. Waiters on [`changed`](Self::changed) are
+    /// notified even though the overall health state itself is unaffected, and can tell why with
+    /// [`is_shutting_down`](Self::is_shutting_down).
+    ///
+    /// Shutdown completes once every [`Component`] and [`Publisher`](crate::Publisher) has been dropped, including the
+    /// publishers of running health checks, or once the grace period has passed, whichever comes first. Use
+    /// [`shutdown_complete`](Self::shutdown_complete) to wait for it.
+    ///
+    /// Calling this method again once shutdown has begun has no effect.
+    pub fn begin_shutdown(&self, grace_period: Duration) {
+        let _ = self.aggregator_tx.send(AggregatorMessage::BeginShutdown(grace_period));
+        self.runtime.run_until_stalled();
+    }
+
+    /// Returns `true` once [`begin_shutdown`](Self::begin_shutdown) has been called.
+    #[must_use]
+    pub fn is_shutting_down(&self) -> bool {
+        self.runtime.run_until_stalled();
+        *self.shutdown_rx.borrow() != Shutdown::Running
+    }
+
+    /// Wait for the shutdown started with [`begin_shutdown`](Self::begin_shutdown) to complete.
+    ///
+    /// This method's future resolves with `true` once every component and publisher has been dropped, or with `false`
+    /// if the grace period passed first. If shutdown hasn't begun yet, the future waits for it to begin.
+    pub async fn shutdown_complete(&self) -> bool {
+        let mut shutdown_rx = self.shutdown_rx.clone();
+        let completed = shutdown_rx.wait_for(|shutdown| matches!(shutdown, Shutdown::Complete { .. }));
+        let result = drive(&*self.runtime, completed).await;
+        result.is_ok_and(|shutdown| shutdown == Shutdown::Complete { drained: true })
+    }
+
     /// Get a health report for each component.
     ///
     /// The filter parameter can be used to control which publisher messages are included in the report.
@@ -68,32 +399,243 @@ impl Aggregator {
     /// This returns `None` if the aggregator has been dropped.
     #[must_use]
     pub async fn reports(&self, filter: Filter) -> Option<Reports> {
-        let (response_tx, response_rx) = oneshot::channel();
-        let msg = AggregatorMessage::GetReport(filter, response_tx);
-        if self.aggregator_tx.send(msg).is_ok() {
-            return response_rx.await.ok();
+        self.request(|response_tx| AggregatorMessage::GetReport(filter, response_tx)).await
+    }
+
+    /// Get the recent state transitions of all components, including child components, merged into a single timeline,
+    /// oldest first.
+    ///
+    /// The number of transitions remembered for each component is set with
+    /// [`ComponentBuilder::history_capacity`](crate::ComponentBuilder::history_capacity).
+    ///
+    /// This returns `None` if the aggregator has been dropped.
+    #[must_use]
+    pub async fn history(&self) -> Option<Vec<Transition>> {
+        let mut history = Vec::new();
+        collect_history(self.reports(Filter::HISTORY).await?, &mut history);
+        history.sort_by_key(Transition::timestamp);
+        Some(history)
+    }
+
+    async fn request<T: Send>(&self, msg: impl FnOnce(oneshot::Sender<T>) -> AggregatorMessage) -> Option<T> {
+        request(&self.aggregator_tx, &*self.runtime, msg).await
+    }
+}
+
+/// Collect the history of reports along with the history of all their descendants.
+fn collect_history(reports: impl IntoIterator<Item = Report>, out: &mut Vec<Transition>) {
+    for report in reports {
+        out.extend(report.history);
+        collect_history(report.children, out);
+    }
+}
+
+/// Get a health report for each component through a weak sender to the aggregator worker.
+///
+/// This returns `None` if the aggregator has been dropped.
+#[cfg(feature = "otel")]
+pub async fn request_reports(
+    aggregator_tx: &mpsc::WeakUnboundedSender<AggregatorMessage>,
+    runtime: &dyn Runtime,
+    filter: Filter,
+) -> Option<Reports> {
+    let channel = aggregator_tx.upgrade()?;
+    request(&channel, runtime, |response_tx| AggregatorMessage::GetReport(filter, response_tx)).await
+}
+
+/// Send a request to the aggregator worker and wait for its response, running the workers in the meantime.
+///
+/// This returns `None` if the aggregator has been dropped.
+async fn request<T: Send>(
+    aggregator_tx: &mpsc::UnboundedSender<AggregatorMessage>,
+    runtime: &dyn Runtime,
+    msg: impl FnOnce(oneshot::Sender<T>) -> AggregatorMessage,
+) -> Option<T> {
+    let (response_tx, response_rx) = oneshot::channel();
+    aggregator_tx.send(msg(response_tx)).ok()?;
+    drive(runtime, response_rx).await.ok()
+}
+
+/// How far along the application is in shutting down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shutdown {
+    Running,
+    Draining,
+    Complete { drained: bool },
+}
+
+/// The views of the application's health maintained by the aggregator worker.
+struct Views {
+    policy: Arc<dyn AggregationPolicy>,
+    health_tx: watch::Sender<Health>,
+    liveness: ProbeView,
+    readiness: ProbeView,
+    health_override: Option<(HealthOverride, Option<Instant>)>,
+    startup_deadline: Option<Instant>,
+    shutdown_tx: watch::Sender<Shutdown>,
+    shutdown_deadline: Option<Instant>,
+    drain_tx: Option<mpsc::UnboundedSender<Infallible>>,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl Views {
+    fn update(&mut self, monitors: &[ComponentMonitor]) {
+        let summaries: Vec<Report> = monitors.iter().filter(|m| m.alive()).map(ComponentMonitor::summary).collect();
+        let forced = self.health_override().map(HealthOverride::state);
+
+        // the application is up as soon as every component has been heard from
+        if !summaries.is_empty() && summaries.iter().all(Report::has_published) {
+            self.end_startup();
+        }
+
+        let starting = self.startup_deadline.is_some();
+        self.health_tx.send(forced.unwrap_or_else(|| self.policy.aggregate(&summaries)));
+        if *self.shutdown_tx.borrow() == Shutdown::Running {
+            self.liveness.update(&*self.policy, &summaries, forced, starting);
+            self.readiness.update(&*self.policy, &summaries, forced, starting);
+        } else {
+            // stop sending traffic to the application, but don't restart it while its components wind down
+            self.liveness.set(ProbeStatus::Pass);
+            self.readiness.set(ProbeStatus::Fail);
         }
+    }
+
+    /// Force the overall health, or go back to the health derived from the components with `None`.
+    fn set_override(&mut self, health_override: Option<HealthOverride>) {
+        let now = self.runtime.now();
+        self.health_override = health_override.map(|mut health_override| {
+            health_override.started = SystemTime::now();
+            let deadline = now.checked_add(health_override.duration());
+            (health_override, deadline)
+        });
+    }
+
+    fn health_override(&self) -> Option<&HealthOverride> {
+        self.health_override.as_ref().map(|(health_override, _)| health_override)
+    }
+
+    /// Returns a future that resolves when the override of the overall health runs out.
+    fn override_expired(&self) -> impl Future<Output = ()> + use<> {
+        self.sleep_until(self.health_override.as_ref().and_then(|(_, deadline)| *deadline))
+    }
+
+    /// Returns a future that resolves when the application's startup grace period runs out.
+    fn startup_expired(&self) -> impl Future<Output = ()> + use<> {
+        self.sleep_until(self.startup_deadline)
+    }
+
+    /// End the application's startup grace period.
+    const fn end_startup(&mut self) {
+        self.startup_deadline = None;
+    }
+
+    /// Start draining the application, returning `false` if it's already shutting down.
+    fn begin_shutdown(&mut self, grace_period: Duration) -> bool {
+        if *self.shutdown_tx.borrow() != Shutdown::Running {
+            return false;
+        }
+
+        // from now on, the drain channel closes as soon as the last component or publisher is dropped
+        self.drain_tx = None;
+        self.shutdown_deadline = self.runtime.now().checked_add(grace_period);
+        self.shutdown_tx.send(Shutdown::Draining);
+        true
+    }
+
+    fn is_draining(&self) -> bool {
+        *self.shutdown_tx.borrow() == Shutdown::Draining
+    }
+
+    /// Returns a future that resolves when the shutdown grace period runs out.
+    fn shutdown_expired(&self) -> impl Future<Output = ()> + use<> {
+        self.sleep_until(self.shutdown_deadline)
+    }
+
+    /// Complete the shutdown, noting whether every component and publisher was dropped in time.
+    fn complete_shutdown(&mut self, drained: bool) {
+        self.shutdown_deadline = None;
+        self.shutdown_tx.send(Shutdown::Complete { drained });
+    }
+
+    fn sleep_until(&self, deadline: Option<Instant>) -> impl Future<Output = ()> + use<> {
+        let sleep = deadline.map(|deadline| self.runtime.sleep_until(deadline));
+
+        async move {
+            match sleep {
+                Some(sleep) => sleep.await,
+                None => pending().await,
+            }
+        }
+    }
+}
+
+/// A probe computed from the components that contribute to it.
+struct ProbeView {
+    probe: Probes,
+    threshold: Health,
+    startup_status: ProbeStatus,
+    status_tx: watch::Sender<ProbeStatus>,
+}
+
+impl ProbeView {
+    fn update(&self, policy: &dyn AggregationPolicy, summaries: &[Report], forced: Option<Health>, starting: bool) {
+        let status = match forced {
+            Some(state) => self.outcome(state),
+            None if starting => self.startup_status,
+            None => {
+                // components that are still starting up can't fail the probe, but they keep it from fully passing
+                let (starting, started): (Vec<Report>, Vec<Report>) = summaries
+                    .iter()
+                    .filter(|s| s.probes().contains(self.probe))
+                    .cloned()
+                    .partition(Report::is_starting);
 
-        None
+                match self.outcome(policy.aggregate(&started)) {
+                    ProbeStatus::Pass if !starting.is_empty() => self.startup_status,
+                    status => status,
+                }
+            }
+        };
+
+        self.set(status);
+    }
+
+    fn set(&self, status: ProbeStatus) {
+        // only wake up waiters when the outcome of the probe actually changes
+        let _ = self.status_tx.send_if_modified(|current| replace(current, status) != status);
+    }
+
+    fn outcome(&self, state: Health) -> ProbeStatus {
+        if state >= self.threshold {
+            ProbeStatus::Fail
+        } else {
+            ProbeStatus::Pass
+        }
     }
 }
 
 async fn aggregator_worker(
     mut aggregator_rx: mpsc::UnboundedReceiver<AggregatorMessage>,
-    health_tx: watch::Sender<Health>,
-    debounce_delay: Duration,
+    mut views: Views,
+    mut dependencies: Dependencies,
+    transitions_tx: broadcast::Sender<ComponentTransition>,
+    mut debouncer: Debouncer,
+    mut drain_rx: mpsc::UnboundedReceiver<Infallible>,
 ) {
     let mut monitors = Vec::new();
-    let mut debouncer = Debouncer::new(debounce_delay);
 
     loop {
         let mut send_update = false;
 
-        tokio::select! {
-            msg = aggregator_rx.recv() => {
+        select! {
+            msg = aggregator_rx.recv().fuse() => {
                 match msg {
                     Some(AggregatorMessage::ComponentCreated(monitor)) => {
                         monitors.push(monitor);
+                        dependencies.propagate(&monitors);
+
+                        // the new component may still be starting up, which holds back readiness
+                        send_update = debouncer.trigger();
                     }
 
                     Some(AggregatorMessage::GetReport(filter, response_tx)) => {
@@ -108,16 +650,75 @@ async fn aggregator_worker(
                         }
 
                         // don't care if the receiver has gone away
-                        let _ = response_tx.send(Reports::new(reports));
+                        let _ = response_tx.send(Reports::new(reports).with_override(views.health_override().cloned()));
+                    }
+
+                    Some(AggregatorMessage::FindComponent(name, response_tx)) => {
+                        let found = monitors
+                            .iter()
+                            .filter(|m| m.alive())
+                            .map(ComponentMonitor::subscribe)
+                            .find(|rx| *rx.borrow().name() == *name);
+
+                        // don't care if the receiver has gone away
+                        let _ = response_tx.send(found);
+                    }
+
+                    Some(AggregatorMessage::AddDependency(dependent, dependency, response_tx)) => {
+                        let result = dependencies.add(dependent, dependency);
+                        if result.is_ok() {
+                            dependencies.propagate(&monitors);
+                        }
+
+                        // don't care if the receiver has gone away
+                        let _ = response_tx.send(result);
+                    }
+
+                    Some(AggregatorMessage::GetDependencyGraph(response_tx)) => {
+                        // don't care if the receiver has gone away
+                        let _ = response_tx.send(dependencies.graph(&monitors));
+                    }
+
+                    Some(AggregatorMessage::SetMaintenance(name, maintenance, response_tx)) => {
+                        let mut found = false;
+                        for monitor in monitors.iter().filter(|m| m.alive() && *m.summary().name == *name) {
+                            monitor.set_maintenance(maintenance.clone());
+                            found = true;
+                        }
+
+                        // don't care if the receiver has gone away
+                        let _ = response_tx.send(found);
+                    }
+
+                    Some(AggregatorMessage::SetOverride(health_override)) => {
+                        // operator decisions take effect right away, rather than waiting for the debouncer
+                        views.set_override(health_override);
+                        send_update = true;
                     }
 
-                    Some(AggregatorMessage::ComponentHealthChanged) => {
+                    Some(AggregatorMessage::BeginShutdown(grace_period)) => {
+                        // readiness fails right away, rather than waiting for the debouncer
+                        send_update = views.begin_shutdown(grace_period);
+                    }
+
+                    Some(AggregatorMessage::ComponentHealthChanged(transition)) => {
+                        if let Some(transition) = transition {
+                            // it's fine if nobody is listening
+                            let _ = transitions_tx.send(*transition);
+                        }
+
+                        // impact is propagated right away, rather than waiting for the debouncer
+                        dependencies.propagate(&monitors);
                         send_update = debouncer.trigger();
                     }
 
                     Some(AggregatorMessage::ComponentDropped) => {
                         // clean up any monitors that are duds
                         monitors.retain(ComponentMonitor::alive);
+                        dependencies.propagate(&monitors);
+
+                        // the departed component no longer contributes to the application's health
+                        send_update = debouncer.trigger();
                     }
 
                     None => {
@@ -127,26 +728,41 @@ async fn aggregator_worker(
                 }
             }
 
-            () = debouncer.ready() => {
+            () = debouncer.ready().fuse() => {
+                send_update = true;
+            }
+
+            () = views.override_expired().fuse() => {
+                views.set_override(None);
+                send_update = true;
+            }
+
+            () = views.startup_expired().fuse() => {
+                views.end_startup();
                 send_update = true;
             }
+
+            () = drained(&mut drain_rx, views.is_draining()).fuse() => {
+                views.complete_shutdown(true);
+            }
+
+            () = views.shutdown_expired().fuse() => {
+                views.complete_shutdown(false);
+            }
         }
 
         if send_update {
-            let _ = health_tx.send(get_aggregate_health_state(&monitors));
+            views.update(&monitors);
         }
     }
 }
 
-fn get_aggregate_health_state(monitors: &[ComponentMonitor]) -> Health {
-    let mut state = Health::Nominal;
-    for monitor in monitors {
-        let component_state = monitor.state();
-        if component_state > state {
-            state = component_state;
-        }
+/// Returns a future that resolves once every component and publisher is gone, or never if the aggregator isn't draining.
+async fn drained(drain_rx: &mut mpsc::UnboundedReceiver<Infallible>, draining: bool) {
+    // nothing is ever sent, so the channel only yields once all of its senders are gone
+    if !draining || drain_rx.recv().await.is_some() {
+        pending::<()>().await;
     }
-    state
 }
 
 impl Default for Aggregator {
@@ -154,3 +770,536 @@ impl Default for Aggregator {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TokioRuntime;
+    use futures_util::StreamExt;
+    use tokio::time::timeout;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn zero_debounce_propagates_immediately() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let mut component = aggregator.component("database");
+        let mut publisher = component.publisher();
+
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+        assert_eq!(component.state(), Health::Critical);
+
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.state(), Health::Critical);
+    }
+
+    #[tokio::test]
+    async fn component_inherits_aggregator_default() {
+        let aggregator = Aggregator::builder().component_debounce_interval(Duration::from_secs(7)).build();
+
+        let builder = aggregator.component_builder("cache");
+        assert_eq!(builder.debounce_interval, Duration::from_secs(7));
+
+        let builder = builder.debounce_interval(Duration::ZERO);
+        assert_eq!(builder.debounce_interval, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn policy_is_applied() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .policy(crate::Quorum::new(2))
+            .build();
+
+        let mut shards: Vec<_> = (0..3).map(|i| aggregator.component(format!("shard-{i}"))).collect();
+        let mut publishers: Vec<_> = shards.iter().map(Component::publisher).collect();
+
+        publishers[0].publish(Health::Down, [("reason", "disk failure")]);
+        timeout(WAIT, shards[0].changed()).await.unwrap().unwrap();
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.state(), Health::Nominal);
+
+        publishers[1].publish(Health::Critical, [("reason", "disk failure")]);
+        timeout(WAIT, shards[1].changed()).await.unwrap().unwrap();
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.state(), Health::Critical);
+    }
+
+    #[tokio::test]
+    async fn probes_use_their_own_components() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let mut warmup = aggregator.component_builder("cache-warmup").probes(Probes::READINESS).build();
+        let mut publisher = warmup.publisher();
+
+        publisher.publish(Health::Down, [("reason", "still warming")]);
+        timeout(WAIT, warmup.changed()).await.unwrap().unwrap();
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+
+        assert_eq!(aggregator.readiness(), ProbeStatus::Fail);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+
+        publisher.publish(Health::Nominal, Vec::<crate::Attribute>::new());
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn probes_use_their_own_thresholds() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .liveness_threshold(Health::Unrecoverable)
+            .readiness_threshold(Health::Degraded)
+            .build();
+
+        let mut component = aggregator.component("database");
+        let mut publisher = component.publisher();
+
+        publisher.publish(Health::Down, [("reason", "failover")]);
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+
+        assert_eq!(aggregator.readiness(), ProbeStatus::Fail);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn history_records_transitions() {
+        let aggregator = Aggregator::builder().component_debounce_interval(Duration::ZERO).build();
+
+        let mut database = aggregator.component("database");
+        let mut cache = aggregator.component_builder("cache").history_capacity(1).build();
+        let mut database_publisher = database.publisher();
+        let mut cache_publisher = cache.publisher();
+
+        database_publisher.publish(Health::Degraded, [("reason", "slow")]);
+        timeout(WAIT, database.changed()).await.unwrap().unwrap();
+        database_publisher.publish(Health::Nominal, Vec::<crate::Attribute>::new());
+        timeout(WAIT, database.changed()).await.unwrap().unwrap();
+
+        cache_publisher.publish(Health::Critical, [("reason", "evicted")]);
+        timeout(WAIT, cache.changed()).await.unwrap().unwrap();
+        cache_publisher.publish(Health::Down, [("reason", "crashed")]);
+        timeout(WAIT, cache.changed()).await.unwrap().unwrap();
+
+        let history = database.history().await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].old_state(), Health::Nominal);
+        assert_eq!(history[0].new_state(), Health::Degraded);
+        assert_eq!(history[0].signal().unwrap().attributes()[0].value(), &"slow".into());
+        assert_eq!(history[1].new_state(), Health::Nominal);
+
+        // the cache only remembers its latest transition
+        let history = aggregator.history().await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2].component(), Some("cache"));
+        assert_eq!(history[2].new_state(), Health::Down);
+
+        // history is only included in reports on request
+        let report = database.report(Filter::ALL).await.unwrap();
+        assert!(report.history().is_empty());
+    }
+
+    #[tokio::test]
+    async fn history_includes_child_components() {
+        let aggregator = Aggregator::builder().component_debounce_interval(Duration::ZERO).build();
+
+        let mut storage = aggregator.component("storage");
+        let mut shard = storage.child("shard-3");
+        let mut publisher = shard.publisher();
+
+        publisher.publish(Health::Critical, [("reason", "disk full")]);
+        timeout(WAIT, shard.changed()).await.unwrap().unwrap();
+        timeout(WAIT, storage.changed()).await.unwrap().unwrap();
+
+        let history = aggregator.history().await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(
+            history
+                .iter()
+                .any(|t| t.component() == Some("storage") && t.new_state() == Health::Critical)
+        );
+        assert!(
+            history
+                .iter()
+                .any(|t| t.component() == Some("storage.shard-3") && t.new_state() == Health::Critical)
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_track_signal_times() {
+        let aggregator = Aggregator::builder().component_debounce_interval(Duration::ZERO).build();
+
+        let mut database = aggregator.component("database");
+        let mut first = database.publisher();
+        let mut second = database.publisher();
+
+        let start = SystemTime::now();
+        first.publish(Health::Degraded, [("reason", "slow")]);
+        timeout(WAIT, database.changed()).await.unwrap().unwrap();
+        let since = database.report(Filter::empty()).await.unwrap().since();
+        assert!(since >= start);
+
+        // the same signal from another publisher, and again from the first, only moves the reaffirmation time
+        std::thread::sleep(Duration::from_millis(10));
+        second.publish(Health::Degraded, [("reason", "slow")]);
+        first.publish(Health::Degraded, [("reason", "slow")]);
+
+        let report = database.report(Filter::ALL).await.unwrap();
+        assert_eq!(report.since(), since);
+
+        let (signal, count) = report.signals(Health::Degraded).next().unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(signal.first_published(), first.signal().first_published());
+        assert_eq!(signal.last_reaffirmed(), first.signal().last_reaffirmed());
+        assert!(signal.last_reaffirmed() > signal.first_published());
+
+        assert!(report.oldest_signal_age(Health::Degraded) >= Some(Duration::from_millis(10)));
+        assert_eq!(report.newest_signal_age(Health::Nominal), None);
+    }
+
+    #[tokio::test]
+    async fn maintenance_limits_impact_until_it_ends() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let database = aggregator.component("database");
+        let mut publisher = database.publisher();
+
+        let maintenance = Maintenance::new()
+            .reason(("reason", "failover"))
+            .expires_after(Duration::from_millis(100));
+        assert!(aggregator.begin_maintenance("database", maintenance).await);
+        assert!(!aggregator.begin_maintenance("cache", Maintenance::new()).await);
+
+        publisher.publish(Health::Down, [("reason", "failing over")]);
+        let report = timeout(WAIT, async {
+            loop {
+                let report = database.report(Filter::empty()).await.unwrap();
+                if report.state() == Health::Down {
+                    break report;
+                }
+
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // the component is still reported as down, but doesn't take the application down with it
+        let maintenance = report.maintenance().unwrap();
+        assert_eq!(maintenance.reason_attribute().unwrap().value(), &"failover".into());
+        assert_eq!(report.effective_state(), Health::Nominal);
+        assert_eq!(aggregator.state(), Health::Nominal);
+
+        // once the maintenance expires, the outage shows
+        timeout(WAIT, async {
+            while aggregator.state() != Health::Down {
+                aggregator.changed().await.unwrap();
+            }
+        })
+        .await
+        .unwrap();
+
+        database.begin_maintenance(Maintenance::new().cap(Health::Degraded));
+        timeout(WAIT, async {
+            while aggregator.state() != Health::Degraded {
+                aggregator.changed().await.unwrap();
+            }
+        })
+        .await
+        .unwrap();
+
+        database.end_maintenance();
+        timeout(WAIT, async {
+            while aggregator.state() != Health::Down {
+                aggregator.changed().await.unwrap();
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn overrides_force_health_until_they_end() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let mut database = aggregator.component("database");
+        let _publisher = database.publisher();
+
+        aggregator.override_health(
+            HealthOverride::new(Health::Critical, Duration::from_millis(100))
+                .by("alice")
+                .because("drain"),
+        );
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Fail);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+        assert_eq!(aggregator.state(), Health::Critical);
+
+        let reports = aggregator.reports(Filter::empty()).await.unwrap();
+        let health_override = reports.health_override().unwrap();
+        assert_eq!(health_override.set_by(), Some("alice"));
+        assert_eq!(health_override.reason(), Some("drain"));
+
+        // the override expires on its own
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Pass);
+        assert!(aggregator.reports(Filter::empty()).await.unwrap().health_override().is_none());
+
+        database.override_health(HealthOverride::new(Health::Down, WAIT).because("testing"));
+        timeout(WAIT, database.changed()).await.unwrap().unwrap();
+        assert_eq!(database.state(), Health::Down);
+
+        let report = database.report(Filter::empty()).await.unwrap();
+        assert_eq!(report.health_override().unwrap().reason(), Some("testing"));
+
+        database.clear_override();
+        timeout(WAIT, database.changed()).await.unwrap().unwrap();
+        assert_eq!(database.state(), Health::Nominal);
+    }
+
+    #[tokio::test]
+    async fn startup_grace_periods_spare_liveness() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .startup_grace_period(WAIT)
+            .build();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Starting);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+
+        let database = aggregator.component("database");
+        let cache = aggregator.component("cache");
+        let mut database_publisher = database.publisher();
+        let mut cache_publisher = cache.publisher();
+
+        database_publisher.publish(Health::Down, [("reason", "connecting")]);
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.state(), Health::Down);
+        assert_eq!(aggregator.readiness(), ProbeStatus::Starting);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+
+        // once every component has been heard from, the probes reflect their health
+        cache_publisher.publish(Health::Nominal, [("reason", "warm")]);
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.liveness(), ProbeStatus::Fail);
+        assert_eq!(aggregator.readiness(), ProbeStatus::Fail);
+
+        database_publisher.publish(Health::Nominal, [("reason", "connected")]);
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Pass);
+
+        // a component's own grace period holds back readiness until it runs out
+        let queue = aggregator
+            .component_builder("queue")
+            .startup_grace_period(Duration::from_millis(100))
+            .build();
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Starting);
+        assert!(queue.report(Filter::empty()).await.unwrap().is_starting());
+
+        timeout(WAIT, aggregator.readiness_changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.readiness(), ProbeStatus::Pass);
+        assert!(!queue.report(Filter::empty()).await.unwrap().is_starting());
+    }
+
+    #[tokio::test]
+    async fn shutdown_drains_readiness_until_everything_is_dropped() {
+        let mut aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let database = aggregator.component("database");
+        let mut publisher = database.publisher();
+        assert!(!aggregator.is_shutting_down());
+
+        aggregator.begin_shutdown(WAIT);
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert!(aggregator.is_shutting_down());
+        assert_eq!(aggregator.readiness(), ProbeStatus::Fail);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+
+        // components winding down don't get the application restarted
+        publisher.publish(Health::Down, [("reason", "disconnected")]);
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert_eq!(aggregator.state(), Health::Down);
+        assert_eq!(aggregator.liveness(), ProbeStatus::Pass);
+
+        // publishers keep the application from being drained, even once their component is gone
+        drop(database);
+        assert!(timeout(Duration::from_millis(50), aggregator.shutdown_complete()).await.is_err());
+
+        drop(publisher);
+        assert!(timeout(WAIT, aggregator.shutdown_complete()).await.unwrap());
+
+        // shutdown gives up on stragglers once the grace period has passed
+        let aggregator = Aggregator::new();
+        let _database = aggregator.component("database");
+        aggregator.begin_shutdown(Duration::from_millis(50));
+        assert!(!timeout(WAIT, aggregator.shutdown_complete()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn children_roll_up_into_parent() {
+        let mut aggregator = Aggregator::builder().component_debounce_interval(Duration::ZERO).build();
+
+        let mut storage = aggregator.component("storage");
+        let mut shard = storage.child("shard-3");
+        let mut publisher = shard.publisher();
+
+        publisher.publish(Health::Critical, [("reason", "disk full")]);
+        timeout(WAIT, shard.changed()).await.unwrap().unwrap();
+        timeout(WAIT, storage.changed()).await.unwrap().unwrap();
+        timeout(WAIT, aggregator.changed()).await.unwrap().unwrap();
+        assert_eq!(storage.state(), Health::Critical);
+        assert_eq!(aggregator.state(), Health::Critical);
+
+        // children are nested in their parent's report, and the filter applies at every depth
+        let reports: Vec<Report> = aggregator.reports(Filter::only(Health::Critical)).await.unwrap().collect();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].children().len(), 1);
+
+        let child = &reports[0].children()[0];
+        assert_eq!(child.name(), "storage.shard-3");
+        assert_eq!(child.signals(Health::Critical).len(), 1);
+
+        let report = storage.report(Filter::empty()).await.unwrap();
+        assert_eq!(report.children()[0].signals(Health::Critical).len(), 0);
+
+        // dropping the child lets the parent recover
+        drop(publisher);
+        drop(shard);
+        timeout(WAIT, storage.changed()).await.unwrap().unwrap();
+        assert_eq!(storage.state(), Health::Nominal);
+        assert!(storage.report(Filter::ALL).await.unwrap().children().is_empty());
+    }
+
+    #[tokio::test]
+    async fn dependencies_propagate_impact() {
+        let aggregator = Aggregator::builder().component_debounce_interval(Duration::ZERO).build();
+
+        let mut api = aggregator.component("api");
+        let mut database = aggregator.component("database");
+        let mut publisher = database.publisher();
+
+        aggregator.add_dependency("api", "database").await.unwrap();
+        let cycle = aggregator.add_dependency("database", "api").await.unwrap_err();
+        assert_eq!(cycle.path().collect::<Vec<_>>(), ["database", "api", "database"]);
+
+        publisher.publish(Health::Down, [("reason", "unreachable")]);
+        timeout(WAIT, database.changed()).await.unwrap().unwrap();
+        timeout(WAIT, api.changed()).await.unwrap().unwrap();
+        assert_eq!(api.state(), Health::Degraded);
+
+        let report = api.report(Filter::ALL).await.unwrap();
+        let (signal, _) = report.signals(Health::Degraded).next().unwrap();
+        let dependency = signal.attributes().iter().find(|a| a.name().as_str() == "dependency").unwrap();
+        assert_eq!(dependency.value(), &"database".into());
+
+        let graph = aggregator.dependency_graph().await.unwrap();
+        assert_eq!(graph.dependencies().collect::<Vec<_>>(), [("api", "database")]);
+        assert_eq!(graph.state("database"), Some(Health::Down));
+        assert!(graph.to_mermaid().contains("c0 --> c1"));
+
+        // the impact goes away once the dependency recovers
+        publisher.publish(Health::Nominal, Vec::<crate::Attribute>::new());
+        timeout(WAIT, api.changed()).await.unwrap().unwrap();
+        assert_eq!(api.state(), Health::Nominal);
+    }
+
+    #[tokio::test]
+    async fn watch_streams_transitions() {
+        let aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let database = aggregator.component("database");
+        let mut overall = Box::pin(aggregator.watch());
+        let mut component = Box::pin(database.watch());
+        let mut publisher = database.publisher();
+
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+
+        let transition = timeout(WAIT, component.next()).await.unwrap().unwrap();
+        assert_eq!(transition.component(), Some("database"));
+        assert_eq!(transition.old_state(), Health::Nominal);
+        assert_eq!(transition.new_state(), Health::Critical);
+
+        let transition = timeout(WAIT, overall.next()).await.unwrap().unwrap();
+        assert_eq!(transition.component(), None);
+        assert_eq!(transition.new_state(), Health::Critical);
+
+        // the streams end along with what they observe
+        drop(publisher);
+        drop(database);
+        let transition = timeout(WAIT, component.next()).await.unwrap().unwrap();
+        assert_eq!(transition.new_state(), Health::Nominal);
+        assert!(timeout(WAIT, component.next()).await.unwrap().is_none());
+
+        drop(aggregator);
+        while timeout(WAIT, overall.next()).await.unwrap().is_some() {}
+    }
+
+    #[tokio::test]
+    async fn flapping_components_are_flagged() {
+        let aggregator = Aggregator::builder().component_debounce_interval(Duration::ZERO).build();
+        let mut component = aggregator
+            .component_builder("network")
+            .flapping_state(Health::Critical)
+            .flap_detection(1, Duration::from_secs(60))
+            .build();
+        let mut publisher = component.publisher();
+
+        publisher.publish(Health::Degraded, [("reason", "packet loss")]);
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+        publisher.publish(Health::Nominal, Vec::<crate::Attribute>::new());
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+
+        let report = component.report(Filter::empty()).await.unwrap();
+        assert!(report.is_flapping());
+        assert_eq!(report.state(), Health::Critical);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lingering_components_escalate() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator
+            .component_builder("database")
+            .escalate(Health::Degraded, Duration::from_secs(300))
+            .build();
+        let mut publisher = component.publisher();
+
+        publisher.publish(Health::Degraded, [("reason", "replica lag")]);
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Degraded);
+
+        // the paused clock jumps ahead to the escalation deadline
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Critical);
+
+        let report = component.report(Filter::empty()).await.unwrap();
+        assert_eq!(report.escalation().map(|e| e.to()), Some(Health::Critical));
+    }
+}
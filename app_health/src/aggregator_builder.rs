@@ -0,0 +1,166 @@
+use crate::{AggregationPolicy, Aggregator, Health, Runtime, WorstOf};
+use core::time::Duration;
+use std::sync::Arc;
+
+/// The default minimum interval between overall health updates produced by an aggregator.
+const DEFAULT_AGGREGATOR_DEBOUNCE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The default minimum interval between health updates produced by a component.
+const DEFAULT_COMPONENT_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Configures and creates an [`Aggregator`].
+///
+/// Builders are created with [`Aggregator::builder`].
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::Aggregator;
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::builder()
+///         .debounce_interval(Duration::from_millis(250))
+///         .component_debounce_interval(Duration::ZERO)
+///         .build();
+///
+///     let _component = aggregator.component("database");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AggregatorBuilder {
+    pub(crate) debounce_interval: Duration,
+    pub(crate) component_debounce_interval: Duration,
+    pub(crate) policy: Arc<dyn AggregationPolicy>,
+    pub(crate) liveness_threshold: Health,
+    pub(crate) readiness_threshold: Health,
+    pub(crate) impact_threshold: Health,
+    pub(crate) impact_state: Health,
+    pub(crate) startup_grace_period: Duration,
+    pub(crate) runtime: Option<Arc<dyn Runtime>>,
+}
+
+impl AggregatorBuilder {
+    pub(crate) fn new() -> Self {
+        Self {
+            debounce_interval: DEFAULT_AGGREGATOR_DEBOUNCE_INTERVAL,
+            component_debounce_interval: DEFAULT_COMPONENT_DEBOUNCE_INTERVAL,
+            policy: Arc::new(WorstOf),
+            liveness_threshold: Health::Down,
+            readiness_threshold: Health::Critical,
+            impact_threshold: Health::Down,
+            impact_state: Health::Degraded,
+            startup_grace_period: Duration::ZERO,
+            runtime: None,
+        }
+    }
+
+    /// Set the minimum interval between updates to the overall health state of the application.
+    ///
+    /// Changes to component health that occur within this interval are coalesced into a single update.
+    /// A zero interval disables debouncing entirely, which is mostly useful in tests. Debouncing is always disabled with
+    /// [`InlineRuntime`](crate::InlineRuntime), which has no background workers to publish deferred updates.
+    ///
+    /// Defaults to one second.
+    #[must_use]
+    pub const fn debounce_interval(mut self, interval: Duration) -> Self {
+        self.debounce_interval = interval;
+        self
+    }
+
+    /// Set the default minimum interval between health updates produced by each component.
+    ///
+    /// Components inherit this value unless it's overridden with
+    /// [`ComponentBuilder::debounce_interval`](crate::ComponentBuilder::debounce_interval).
+    ///
+    /// Defaults to 100 milliseconds.
+    #[must_use]
+    pub const fn component_debounce_interval(mut self, interval: Duration) -> Self {
+        self.component_debounce_interval = interval;
+        self
+    }
+
+    /// Set the policy used to compute the overall health of the application from the health of its components.
+    ///
+    /// Defaults to [`WorstOf`].
+    #[must_use]
+    pub fn policy(mut self, policy: impl AggregationPolicy + 'static) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Set the health state at which the liveness probe starts failing.
+    ///
+    /// Defaults to [`Health::Down`].
+    #[must_use]
+    pub const fn liveness_threshold(mut self, threshold: Health) -> Self {
+        self.liveness_threshold = threshold;
+        self
+    }
+
+    /// Set the health state at which the readiness probe starts failing.
+    ///
+    /// Defaults to [`Health::Critical`].
+    #[must_use]
+    pub const fn readiness_threshold(mut self, threshold: Health) -> Self {
+        self.readiness_threshold = threshold;
+        self
+    }
+
+    /// Set the health state at which a component starts impacting the components that depend on it.
+    ///
+    /// Dependencies are declared with [`Aggregator::add_dependency`].
+    ///
+    /// Defaults to [`Health::Down`].
+    #[must_use]
+    pub const fn impact_threshold(mut self, threshold: Health) -> Self {
+        self.impact_threshold = threshold;
+        self
+    }
+
+    /// Set the health state of the signal added to a component while one of its dependencies is impacting it.
+    ///
+    /// Defaults to [`Health::Degraded`].
+    #[must_use]
+    pub const fn impact_state(mut self, state: Health) -> Self {
+        self.impact_state = state;
+        self
+    }
+
+    /// Set how long the application is given to start up before its liveness probe can fail.
+    ///
+    /// During the grace period, the liveness probe passes and the readiness probe reports
+    /// [`ProbeStatus::Starting`](crate::ProbeStatus::Starting), whatever the health of the components. The grace period
+    /// ends early once every component has published a signal or sent a heartbeat. Individual components can be given
+    /// their own grace period with [`ComponentBuilder::startup_grace_period`](crate::ComponentBuilder::startup_grace_period).
+    ///
+    /// Defaults to zero, which disables the grace period.
+    #[must_use]
+    pub const fn startup_grace_period(mut self, grace_period: Duration) -> Self {
+        self.startup_grace_period = grace_period;
+        self
+    }
+
+    /// Set the runtime used to run the background workers of the aggregator and its components.
+    ///
+    /// Defaults to [`TokioRuntime`](crate::TokioRuntime) when the `tokio` feature is enabled, to
+    /// [`ThreadRuntime`](crate::ThreadRuntime) when the `threads` feature is enabled without it, and to
+    /// [`InlineRuntime`](crate::InlineRuntime) when `inline` is the only runtime feature enabled.
+    #[must_use]
+    pub fn runtime(mut self, runtime: impl Runtime + 'static) -> Self {
+        self.runtime = Some(Arc::new(runtime));
+        self
+    }
+
+    /// Create the aggregator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configured [`Runtime`](crate::Runtime) can't spawn the aggregator's worker, such as when using the
+    /// default [`TokioRuntime`](crate::TokioRuntime) from outside of a Tokio runtime.
+    #[must_use]
+    pub fn build(self) -> Aggregator {
+        Aggregator::from_builder(&self)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::sync::oneshot;
+use core::panic::AssertUnwindSafe;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::io;
+use std::panic::catch_unwind;
+use std::sync::Arc;
+use std::thread;
+
+/// Runs the blocking calls of a check on a thread of their own.
+///
+/// File system calls can hang indefinitely, such as on an unresponsive network mount. Running them on a separate thread
+/// keeps them from stalling the executor, and lets the check's timeout fire while they're stuck. This works the same
+/// with every [`Runtime`](crate::Runtime), since it doesn't rely on the executor having a blocking thread pool.
+///
+/// At most one call is outstanding at a time. While a previous call is still stuck, later calls fail right away rather
+/// than piling up more stuck threads.
+#[derive(Debug, Default)]
+pub struct Blocking {
+    busy: Arc<AtomicBool>,
+}
+
+impl Blocking {
+    /// Run the given call on a separate thread and wait for its result.
+    pub async fn run<T: Send + 'static>(&self, call: impl FnOnce() -> io::Result<T> + Send + 'static) -> io::Result<T> {
+        if self.busy.swap(true, Ordering::AcqRel) {
+            return Err(io::Error::other("a previous check is still blocked"));
+        }
+
+        let busy = Arc::clone(&self.busy);
+        let (result_tx, result_rx) = oneshot::channel();
+        let spawned = thread::Builder::new().name("app_health-check".into()).spawn(move || {
+            let result = catch_unwind(AssertUnwindSafe(call)).unwrap_or_else(|_| Err(io::Error::other("the check panicked")));
+            busy.store(false, Ordering::Release);
+
+            // the check may have timed out and stopped waiting
+            let _ = result_tx.send(result);
+        });
+
+        if let Err(e) = spawned {
+            self.busy.store(false, Ordering::Release);
+            return Err(e);
+        }
+
+        result_rx
+            .await
+            .unwrap_or_else(|_| Err(io::Error::other("the check's thread exited unexpectedly")))
+    }
+}
+
+impl Clone for Blocking {
+    /// Create a new runner, since clones of a check run independently of each other.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[tokio::test]
+    async fn runs_one_call_at_a_time() {
+        let blocking = Blocking::default();
+        assert_eq!(blocking.run(|| Ok(42)).await.unwrap(), 42);
+
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let stuck = tokio::time::timeout(
+            core::time::Duration::from_millis(10),
+            blocking.run(move || release_rx.recv().map_err(io::Error::other)),
+        )
+        .await;
+        assert!(stuck.is_err());
+
+        // the stuck call keeps later calls from starting another thread
+        assert!(blocking.run(|| Ok(())).await.is_err());
+        assert!(blocking.clone().run(|| Ok(())).await.is_ok());
+
+        release_tx.send(()).unwrap();
+        while blocking.busy.load(Ordering::Acquire) {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(blocking.run(|| Ok(())).await.is_ok());
+    }
+}
@@ -0,0 +1,13 @@
+use core::fmt::Display;
+
+/// The error returned when waiting for changes to an aggregator or component that has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Closed;
+
+impl Display for Closed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the observed aggregator or component has been dropped")
+    }
+}
+
+impl core::error::Error for Closed {}
@@ -1,59 +1,175 @@
 use crate::aggregator::AggregatorMessage;
 use crate::component_monitor::ComponentMonitor;
 use crate::component_state::ComponentState;
+use crate::component_transition::ComponentTransition;
 use crate::debouncer::Debouncer;
+use crate::drain_guard::DrainGuard;
+use crate::hysteresis::{FlapDetection, Hysteresis};
+use crate::leases::Leases;
+use crate::runtime::{drive, drive_stream};
 use crate::signal::Signal;
-use crate::{Filter, Health, Publisher, Report};
+use crate::sync::{mpsc, oneshot, watch};
+use crate::transition::transitions;
+use crate::{
+    AggregationPolicy, Closed, ComponentBuilder, Filter, Health, HealthCheck, HealthCheckBuilder, HealthOverride, Maintenance, Publisher,
+    Report, Runtime, Transition,
+};
 use core::time::Duration;
+use futures_util::{FutureExt, Stream, select};
 use std::sync::Arc;
-use tokio::sync::{mpsc, oneshot, watch};
 
 /// A component responsible for tracking the health of an individual feature in an application.
 #[derive(Debug, Clone)]
 #[expect(clippy::struct_field_names, reason = "field names are clear and unambiguous")]
 pub struct Component {
     component_tx: mpsc::UnboundedSender<ComponentMessage>,
-    health_rx: watch::Receiver<Health>,
+    health_rx: watch::Receiver<Report>,
     aggregator_tx: mpsc::WeakUnboundedSender<AggregatorMessage>,
+    parent_tx: Option<mpsc::WeakUnboundedSender<ComponentMessage>>,
+    debounce_interval: Duration,
+    policy: Arc<dyn AggregationPolicy>,
+    drain_guard: DrainGuard,
+    runtime: Arc<dyn Runtime>,
 }
 
 /// Messages sent to the component worker.
 pub enum ComponentMessage {
     StartPublishing(Signal),
     ChangeHealth(Signal, Signal),
+    ReaffirmSignal(Signal),
     StopPublishing(Signal),
+    StartLease(u64, Duration, Signal),
+    RenewLease(u64, Option<Signal>),
+    StopLease(u64),
     GetReport(Filter, oneshot::Sender<Report>),
+    ChildCreated(ComponentMonitor),
+    ChildChanged,
+    SetUpstreamSignals(Vec<Signal>),
+    SetMaintenance(Option<Maintenance>),
+    SetOverride(Option<HealthOverride>),
 }
 
 impl Component {
-    pub(crate) fn new(name: impl AsRef<str>, aggregator_tx: mpsc::WeakUnboundedSender<AggregatorMessage>) -> Self {
+    pub(crate) fn new(builder: ComponentBuilder) -> Self {
         let (component_tx, component_rx) = mpsc::unbounded_channel::<ComponentMessage>();
-        let (health_tx, health_rx) = watch::channel(Health::Nominal);
-        let name = name.as_ref().into();
+        let mut component_state = ComponentState::new(
+            builder.name,
+            builder.criticality,
+            builder.probes,
+            builder.history_capacity,
+            Hysteresis::new(
+                builder.recovery_delay,
+                builder.flap_detection.map(|flap| FlapDetection {
+                    state: builder.flapping_state,
+                    ..flap
+                }),
+                builder.escalations,
+                Arc::clone(&builder.runtime),
+            ),
+            Arc::clone(&builder.policy),
+            Arc::clone(&builder.runtime),
+        );
+        component_state.begin_startup(builder.startup_grace_period);
+        let (health_tx, health_rx) = watch::channel(component_state.make_report(Filter::empty()));
+        let aggregator_tx = builder.aggregator_tx;
 
-        drop(tokio::spawn(component_worker(name, component_rx, health_tx, aggregator_tx.clone())));
+        builder.runtime.spawn(Box::pin(component_worker(
+            component_state,
+            Leases::new(builder.stale_signal, Arc::clone(&builder.runtime)),
+            component_rx,
+            health_tx,
+            aggregator_tx.clone(),
+            builder.parent_tx.clone(),
+            Debouncer::new(builder.debounce_interval, Arc::clone(&builder.runtime)),
+        )));
 
         let result = Self {
             component_tx,
             health_rx,
             aggregator_tx,
+            parent_tx: builder.parent_tx,
+            debounce_interval: builder.debounce_interval,
+            policy: builder.policy,
+            drain_guard: builder.drain_guard,
+            runtime: builder.runtime,
         };
 
+        // children are tracked by their parent rather than directly by the aggregator
         let monitor = result.monitor();
-        if let Some(channel) = result.aggregator_tx.upgrade() {
+        if let Some(parent_tx) = &result.parent_tx {
+            if let Some(channel) = parent_tx.upgrade() {
+                let _ = channel.send(ComponentMessage::ChildCreated(monitor));
+            }
+        } else if let Some(channel) = result.aggregator_tx.upgrade() {
             let _ = channel.send(AggregatorMessage::ComponentCreated(monitor));
         }
 
         result
     }
 
+    /// Create a child component with default settings.
+    ///
+    /// See [`child_builder`](Self::child_builder).
+    #[must_use]
+    pub fn child(&self, name: impl AsRef<str>) -> Self {
+        self.child_builder(name).build()
+    }
+
+    /// Create a builder to configure a new child component.
+    ///
+    /// A child's health rolls up into its parent's health using the parent's rollup policy, which defaults to the
+    /// aggregator's policy. The parent's own health is the worse of its publishers' signals and the rolled-up health of its
+    /// children. Children are named after their parent using a dotted path, such as `storage.shard-3`, and their
+    /// reports are nested within the parent's report, see [`Report::children`]. Children can have children of
+    /// their own.
+    ///
+    /// The builder starts out with the parent's debounce interval and rollup policy.
+    #[must_use]
+    pub fn child_builder(&self, name: impl AsRef<str>) -> ComponentBuilder {
+        let mut builder = ComponentBuilder::new(
+            format!("{}.{}", self.health_rx.borrow().name(), name.as_ref()),
+            self.aggregator_tx.clone(),
+            self.debounce_interval,
+            Arc::clone(&self.policy),
+            self.drain_guard.clone(),
+            Arc::clone(&self.runtime),
+        );
+
+        builder.parent_tx = Some(self.component_tx.downgrade());
+        builder
+    }
+
     /// Create a publisher for this component.
     ///
     /// A publisher is how health information is injected into a component. A component's health
     /// is determined by the aggregate health of all its active publishers.
     #[must_use]
     pub fn publisher(&self) -> Publisher {
-        Publisher::new(self.component_tx.downgrade())
+        Publisher::new(self.component_tx.downgrade(), self.drain_guard.clone(), Arc::clone(&self.runtime))
+    }
+
+    /// Create a publisher for this component that must keep reporting in to be trusted.
+    ///
+    /// If the publisher goes longer than `ttl` without publishing or calling [`heartbeat`](Publisher::heartbeat),
+    /// the component assumes the publisher is stuck and replaces its signal with the stale signal configured with
+    /// [`ComponentBuilder::stale_signal`]. The publisher's own signal is restored as soon as it reports in again.
+    #[must_use]
+    pub fn publisher_with_ttl(&self, ttl: Duration) -> Publisher {
+        Publisher::with_ttl(
+            self.component_tx.downgrade(),
+            ttl,
+            self.drain_guard.clone(),
+            Arc::clone(&self.runtime),
+        )
+    }
+
+    /// Register a check that actively probes the health of this component.
+    ///
+    /// The check feeds its results into a publisher of its own once it's started with
+    /// [`HealthCheckBuilder::start`].
+    #[must_use]
+    pub fn health_check<C: HealthCheck + 'static>(&self, check: C) -> HealthCheckBuilder<C> {
+        HealthCheckBuilder::new(check, self.publisher(), Arc::clone(&self.runtime), self.health_rx.clone())
     }
 
     /// Track changes to the component's health state over time.
@@ -71,8 +187,20 @@ impl Component {
     /// # Errors
     ///
     /// The only reason why this future ever fails is when the associated component has been dropped.
-    pub async fn changed(&mut self) -> Result<(), ()> {
-        self.health_rx.changed().await.map_err(|_e| ())
+    pub async fn changed(&mut self) -> Result<(), Closed> {
+        drive(&*self.runtime, self.health_rx.changed()).await.map_err(|_e| Closed)
+    }
+
+    /// Get a stream of the transitions of the component's health state from one state to another.
+    ///
+    /// Each item carries the component's name along with the old and new state. Quick successions of changes are
+    /// debounced, so intermediate states may be skipped. The stream ends once the component has been dropped.
+    pub fn watch(&self) -> impl Stream<Item = Transition> + Send + use<> {
+        let name = Arc::clone(&self.health_rx.borrow().name);
+        drive_stream(
+            Arc::clone(&self.runtime),
+            transitions(self.health_rx.clone(), Some(name), Report::state),
+        )
     }
 
     /// Get the overall health state of the component.
@@ -80,7 +208,8 @@ impl Component {
     /// The overall health is determined by the most severe health state reported by any publisher.
     #[must_use]
     pub fn state(&self) -> Health {
-        *self.health_rx.borrow()
+        self.runtime.run_until_stalled();
+        self.health_rx.borrow().state()
     }
 
     /// Get a health report for the component.
@@ -91,50 +220,81 @@ impl Component {
     #[must_use]
     pub async fn report(&self, filter: Filter) -> Option<Report> {
         let (response_tx, response_rx) = oneshot::channel();
-        let msg = ComponentMessage::GetReport(filter, response_tx);
-        if self.component_tx.send(msg).is_ok() {
-            return response_rx.await.ok();
-        }
+        self.component_tx.send(ComponentMessage::GetReport(filter, response_tx)).ok()?;
+        drive(&*self.runtime, response_rx).await.ok()
+    }
 
-        None
+    /// Get the component's recent state transitions, oldest first.
+    ///
+    /// This returns `None` if the associated component has been dropped.
+    #[must_use]
+    pub async fn history(&self) -> Option<Vec<Transition>> {
+        self.report(Filter::HISTORY).await.map(|r| r.history)
+    }
+
+    /// Put the component into maintenance, limiting its impact on the application's overall health.
+    ///
+    /// The maintenance replaces any maintenance the component is already in, and ends on its own once its duration has
+    /// passed. See [`Maintenance`] for details.
+    pub fn begin_maintenance(&self, maintenance: Maintenance) {
+        let _ = self.component_tx.send(ComponentMessage::SetMaintenance(Some(maintenance)));
+        self.runtime.run_until_stalled();
+    }
+
+    /// Take the component out of maintenance ahead of time.
+    pub fn end_maintenance(&self) {
+        let _ = self.component_tx.send(ComponentMessage::SetMaintenance(None));
+        self.runtime.run_until_stalled();
+    }
+
+    /// Force the component's state, regardless of its publishers' signals.
+    ///
+    /// The override replaces any override already in effect, and ends on its own once its duration has passed. See
+    /// [`HealthOverride`] for details.
+    pub fn override_health(&self, health_override: HealthOverride) {
+        let _ = self.component_tx.send(ComponentMessage::SetOverride(Some(health_override)));
+        self.runtime.run_until_stalled();
+    }
+
+    /// Remove the override of the component's state ahead of time.
+    pub fn clear_override(&self) {
+        let _ = self.component_tx.send(ComponentMessage::SetOverride(None));
+        self.runtime.run_until_stalled();
     }
 }
 
 async fn component_worker(
-    name: Arc<str>,
+    mut component_state: ComponentState,
+    mut leases: Leases,
     mut component_rx: mpsc::UnboundedReceiver<ComponentMessage>,
-    health_tx: watch::Sender<Health>,
+    health_tx: watch::Sender<Report>,
     aggregator_tx: mpsc::WeakUnboundedSender<AggregatorMessage>,
+    parent_tx: Option<mpsc::WeakUnboundedSender<ComponentMessage>>,
+    mut debouncer: Debouncer,
 ) {
-    let mut component_state = ComponentState::new(name);
-    let mut health_state = Health::Nominal;
-    let mut debouncer = Debouncer::new(Duration::from_millis(100));
+    let mut summary = health_tx.borrow().clone();
 
     loop {
         let mut send_update = false;
 
-        tokio::select! {
-            msg = component_rx.recv() => {
+        select! {
+            msg = component_rx.recv().fuse() => {
                 match msg {
-                    Some(ComponentMessage::StartPublishing(health)) => {
-                        component_state.add_publisher_signal(health);
-                        send_update = debouncer.trigger();
-                    }
-
-                    Some(ComponentMessage::ChangeHealth(old_health, new_health)) => {
-                        component_state.remove_publisher_signal(old_health);
-                        component_state.add_publisher_signal(new_health);
-                        send_update = debouncer.trigger();
-                    }
+                    Some(ComponentMessage::GetReport(filter, response_tx)) => {
+                        let mut report = component_state.make_report(filter);
+                        for child in component_state.children() {
+                            if let Some(child_report) = child.report(filter).await {
+                                report.children.push(child_report);
+                            }
+                        }
 
-                    Some(ComponentMessage::StopPublishing(health)) => {
-                        component_state.remove_publisher_signal(health);
-                        send_update = debouncer.trigger();
+                        let _ = response_tx.send(report);
                     }
 
-                    Some(ComponentMessage::GetReport(filter, response_tx)) => {
-                        let report = component_state.make_report(filter);
-                        let _ = response_tx.send(report);
+                    Some(msg) => {
+                        if apply_message(msg, &mut component_state, &mut leases) {
+                            send_update = debouncer.trigger();
+                        }
                     }
 
                     None => {
@@ -144,29 +304,117 @@ async fn component_worker(
                 }
             }
 
-            () = debouncer.ready() => {
+            () = debouncer.ready().fuse() => {
                 send_update = true;
             }
+
+            () = component_state.expired().fuse() => {
+                component_state.refresh();
+                send_update = debouncer.trigger();
+            }
+
+            () = component_state.startup_expired().fuse() => {
+                component_state.end_startup();
+                send_update = debouncer.trigger();
+            }
+
+            () = component_state.maintenance_expired().fuse() => {
+                component_state.set_maintenance(None);
+                send_update = debouncer.trigger();
+            }
+
+            () = component_state.override_expired().fuse() => {
+                component_state.set_override(None);
+                send_update = debouncer.trigger();
+            }
+
+            () = leases.expired().fuse() => {
+                if leases.expire(&mut component_state) {
+                    send_update = debouncer.trigger();
+                }
+            }
         }
 
         if send_update {
+            let old_state = summary.state;
             let new_state = component_state.state();
 
             // We don't send updates if the previous state was nominal and the new state is also nominal.
             // Any other transition is reported, since the publisher messages may have changed
-            if new_state != health_state || new_state != Health::Nominal {
-                health_state = new_state;
-                let _ = health_tx.send(new_state);
+            if new_state != old_state
+                || new_state != Health::Nominal
+                || markers_changed(&summary, &component_state.make_report(Filter::empty()))
+            {
+                let transition = (new_state != old_state).then(|| {
+                    let report = component_state.make_report(Filter::only(new_state));
+                    component_state.record_transition(old_state, new_state, report.dominant_signal().cloned());
+
+                    Box::new(ComponentTransition { old_state, report })
+                });
+
+                summary = component_state.make_report(Filter::empty());
+                health_tx.send(summary.clone());
 
                 // it's OK if the aggregator is no longer there...
                 if let Some(channel) = aggregator_tx.upgrade() {
-                    let _ = channel.send(AggregatorMessage::ComponentHealthChanged);
+                    let _ = channel.send(AggregatorMessage::ComponentHealthChanged(transition));
+                }
+
+                if let Some(channel) = parent_tx.as_ref().and_then(mpsc::WeakUnboundedSender::upgrade) {
+                    let _ = channel.send(ComponentMessage::ChildChanged);
                 }
             }
         }
     }
 }
 
+/// Whether a summary differs from the previous one in anything worth an update besides its state.
+fn markers_changed(old: &Report, new: &Report) -> bool {
+    old.flapping != new.flapping
+        || old.maintenance != new.maintenance
+        || old.health_override != new.health_override
+        || old.starting != new.starting
+        || old.published != new.published
+}
+
+/// Apply a message that updates the component's state, returning `true` if the state may have changed.
+fn apply_message(msg: ComponentMessage, component_state: &mut ComponentState, leases: &mut Leases) -> bool {
+    match msg {
+        ComponentMessage::StartPublishing(health) => component_state.add_publisher_signal(health),
+
+        ComponentMessage::ChangeHealth(old_health, new_health) => {
+            let _ = component_state.record_publication();
+            component_state.remove_publisher_signal(old_health);
+            component_state.add_publisher_signal(new_health);
+        }
+
+        ComponentMessage::ReaffirmSignal(signal) => {
+            // the state is unchanged, only the signal's timestamps move on, unless it's the component's first publication
+            component_state.reaffirm_signal(&signal);
+            return component_state.record_publication();
+        }
+
+        ComponentMessage::StopPublishing(health) => component_state.remove_publisher_signal(health),
+        ComponentMessage::StartLease(id, ttl, signal) => leases.start(id, ttl, signal, component_state),
+        ComponentMessage::RenewLease(id, signal) => {
+            let first = component_state.record_publication();
+            return leases.renew(id, signal, component_state) || first;
+        }
+
+        ComponentMessage::StopLease(id) => leases.stop(id, component_state),
+        ComponentMessage::ChildCreated(monitor) => component_state.add_child(monitor),
+        ComponentMessage::ChildChanged => component_state.refresh_children(),
+        ComponentMessage::SetUpstreamSignals(signals) => return component_state.set_upstream_signals(signals),
+        ComponentMessage::SetMaintenance(maintenance) => component_state.set_maintenance(maintenance),
+        ComponentMessage::SetOverride(health_override) => component_state.set_override(health_override),
+
+        // reports are gathered by the worker itself, since that involves waiting on the children
+        ComponentMessage::GetReport(..) => return false,
+    }
+
+    true
+}
+
 impl Drop for Component {
     fn drop(&mut self) {
         // tell the aggregator we're going away, but we don't care if
@@ -174,5 +422,9 @@ impl Drop for Component {
         if let Some(channel) = self.aggregator_tx.upgrade() {
             let _ = channel.send(AggregatorMessage::ComponentDropped);
         }
+
+        if let Some(channel) = self.parent_tx.as_ref().and_then(mpsc::WeakUnboundedSender::upgrade) {
+            let _ = channel.send(ComponentMessage::ChildChanged);
+        }
     }
 }
@@ -0,0 +1,232 @@
+use crate::aggregator::AggregatorMessage;
+use crate::component::ComponentMessage;
+use crate::drain_guard::DrainGuard;
+use crate::hysteresis::FlapDetection;
+use crate::signal::Signal;
+use crate::sync::mpsc;
+use crate::{AggregationPolicy, Attribute, Component, Criticality, Escalation, Health, Probes, Runtime};
+use core::time::Duration;
+use std::sync::Arc;
+
+/// The default number of transitions remembered by a component.
+const DEFAULT_HISTORY_CAPACITY: usize = 32;
+
+/// Configures and creates a [`Component`].
+///
+/// Builders are created with [`Aggregator::component_builder`](crate::Aggregator::component_builder) and
+/// start out with the defaults configured on the aggregator.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::Aggregator;
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///
+///     // this subsystem is noisy, so we debounce its updates over a longer window
+///     let _component = aggregator
+///         .component_builder("network")
+///         .debounce_interval(Duration::from_secs(5))
+///         .build();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComponentBuilder {
+    pub(crate) name: Arc<str>,
+    pub(crate) aggregator_tx: mpsc::WeakUnboundedSender<AggregatorMessage>,
+    pub(crate) debounce_interval: Duration,
+    pub(crate) criticality: Criticality,
+    pub(crate) probes: Probes,
+    pub(crate) stale_signal: Signal,
+    pub(crate) history_capacity: usize,
+    pub(crate) recovery_delay: Duration,
+    pub(crate) flap_detection: Option<FlapDetection>,
+    pub(crate) flapping_state: Option<Health>,
+    pub(crate) escalations: Vec<Escalation>,
+    pub(crate) startup_grace_period: Duration,
+    pub(crate) policy: Arc<dyn AggregationPolicy>,
+    pub(crate) parent_tx: Option<mpsc::WeakUnboundedSender<ComponentMessage>>,
+    pub(crate) drain_guard: DrainGuard,
+    pub(crate) runtime: Arc<dyn Runtime>,
+}
+
+impl ComponentBuilder {
+    pub(crate) fn new(
+        name: impl AsRef<str>,
+        aggregator_tx: mpsc::WeakUnboundedSender<AggregatorMessage>,
+        debounce_interval: Duration,
+        policy: Arc<dyn AggregationPolicy>,
+        drain_guard: DrainGuard,
+        runtime: Arc<dyn Runtime>,
+    ) -> Self {
+        Self {
+            name: name.as_ref().into(),
+            aggregator_tx,
+            debounce_interval,
+            criticality: Criticality::Required,
+            probes: Probes::all(),
+            stale_signal: Signal::new(Health::Critical, [("stale", true)]),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            recovery_delay: Duration::ZERO,
+            flap_detection: None,
+            flapping_state: None,
+            escalations: Vec::new(),
+            startup_grace_period: Duration::ZERO,
+            policy,
+            parent_tx: None,
+            drain_guard,
+            runtime,
+        }
+    }
+
+    /// Set the minimum interval between health updates produced by the component.
+    ///
+    /// Changes to publisher signals that occur within this interval are coalesced into a single update.
+    /// A zero interval disables debouncing entirely, which is mostly useful in tests.
+    ///
+    /// Defaults to the value configured with
+    /// [`AggregatorBuilder::component_debounce_interval`](crate::AggregatorBuilder::component_debounce_interval).
+    #[must_use]
+    pub const fn debounce_interval(mut self, interval: Duration) -> Self {
+        self.debounce_interval = interval;
+        self
+    }
+
+    /// Set how much the component is allowed to affect the overall health of the application.
+    ///
+    /// Defaults to [`Criticality::Required`].
+    #[must_use]
+    pub const fn criticality(mut self, criticality: Criticality) -> Self {
+        self.criticality = criticality;
+        self
+    }
+
+    /// Set the probes the component contributes to.
+    ///
+    /// Defaults to [`Probes::all`].
+    #[must_use]
+    pub const fn probes(mut self, probes: Probes) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Set the signal that replaces the signal of a publisher that has gone silent for longer than its time-to-live.
+    ///
+    /// Defaults to [`Health::Critical`] with a `stale=true` attribute.
+    ///
+    /// See [`Component::publisher_with_ttl`].
+    #[must_use]
+    pub fn stale_signal(mut self, state: Health, attributes: impl IntoIterator<Item = impl Into<Attribute>>) -> Self {
+        self.stale_signal = Signal::new(state, attributes);
+        self
+    }
+
+    /// Set the number of recent state transitions remembered by the component.
+    ///
+    /// Once the history is full, the oldest transition is discarded to make room for each new one.
+    /// A capacity of zero disables the history.
+    ///
+    /// Defaults to 32.
+    #[must_use]
+    pub const fn history_capacity(mut self, capacity: usize) -> Self {
+        self.history_capacity = capacity;
+        self
+    }
+
+    /// Set how long the component is given to start up before it counts toward the liveness probe.
+    ///
+    /// While it's starting, the component's health is reported as usual, but it doesn't count toward
+    /// [`Aggregator::liveness`](crate::Aggregator::liveness), and it makes [`Aggregator::readiness`](crate::Aggregator::readiness)
+    /// report [`ProbeStatus::Starting`](crate::ProbeStatus::Starting) unless readiness fails anyway. This keeps a
+    /// component that's unhealthy while establishing its connections from getting the application restarted.
+    ///
+    /// The grace period ends early as soon as one of the component's publishers publishes a signal or sends a heartbeat.
+    ///
+    /// Defaults to zero, which disables the grace period.
+    #[must_use]
+    pub const fn startup_grace_period(mut self, grace_period: Duration) -> Self {
+        self.startup_grace_period = grace_period;
+        self
+    }
+
+    /// Set how long the component must remain in a better state before it's reported as having recovered.
+    ///
+    /// Worsening health is always reported right away, but a component that improves is held at its previous
+    /// state until the improvement has lasted for this long. This keeps a component that briefly recovers
+    /// in between failures from bouncing back and forth.
+    ///
+    /// Defaults to zero, which reports recoveries right away.
+    #[must_use]
+    pub const fn recovery_delay(mut self, delay: Duration) -> Self {
+        self.recovery_delay = delay;
+        self
+    }
+
+    /// Consider the component to be flapping when its state changes more than `max_changes` times within `window`.
+    ///
+    /// A flapping component is flagged in its reports with [`Report::is_flapping`](crate::Report::is_flapping),
+    /// and can also be escalated with [`flapping_state`](Self::flapping_state).
+    ///
+    /// Flap detection is disabled by default.
+    #[must_use]
+    pub const fn flap_detection(mut self, max_changes: usize, window: Duration) -> Self {
+        self.flap_detection = Some(FlapDetection {
+            max_changes,
+            window,
+            state: None,
+        });
+        self
+    }
+
+    /// Set the minimum health state reported while the component is flapping.
+    ///
+    /// This has no effect unless flap detection is enabled with [`flap_detection`](Self::flap_detection), which can be
+    /// called either before or after this method.
+    ///
+    /// By default, flapping components are flagged but not escalated.
+    #[must_use]
+    pub const fn flapping_state(mut self, state: Health) -> Self {
+        self.flapping_state = Some(state);
+        self
+    }
+
+    /// Report the component in the next worse state once it has been in the `from` state continuously for the `after` duration.
+    ///
+    /// For example, `escalate(Health::Degraded, Duration::from_secs(300))` reports a component that has been `Degraded`
+    /// for five minutes as `Critical`. Each state can have a single rule, so adding a rule for a state that
+    /// already has one replaces it. The rule in effect is available with [`Report::escalation`](crate::Report::escalation).
+    ///
+    /// There are no escalation rules by default.
+    #[must_use]
+    pub fn escalate(mut self, from: Health, after: Duration) -> Self {
+        self.escalations.retain(|rule| rule.from() != from);
+        self.escalations.push(Escalation::new(from, after));
+        self
+    }
+
+    /// Set the policy used to roll up the health of the component's children into its own health.
+    ///
+    /// Defaults to the policy configured with [`AggregatorBuilder::policy`](crate::AggregatorBuilder::policy),
+    /// or to the parent's rollup policy for child components.
+    ///
+    /// See [`Component::child`].
+    #[must_use]
+    pub fn rollup_policy(mut self, policy: impl AggregationPolicy + 'static) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Create the component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the aggregator's [`Runtime`](crate::Runtime) can't spawn the component's worker, such as when using the
+    /// default [`TokioRuntime`](crate::TokioRuntime) from outside of a Tokio runtime.
+    #[must_use]
+    pub fn build(self) -> Component {
+        Component::new(self)
+    }
+}
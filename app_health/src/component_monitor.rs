@@ -1,6 +1,7 @@
 use crate::component::ComponentMessage;
-use crate::{Filter, Health, Report};
-use tokio::sync::{mpsc, oneshot, watch};
+use crate::signal::Signal;
+use crate::sync::{mpsc, oneshot, watch};
+use crate::{Filter, Maintenance, Report};
 
 /// Monitors the health of a component.
 ///
@@ -9,20 +10,39 @@ use tokio::sync::{mpsc, oneshot, watch};
 #[derive(Debug)]
 pub struct ComponentMonitor {
     component_tx: mpsc::WeakUnboundedSender<ComponentMessage>,
-    health_rx: watch::Receiver<Health>,
+    health_rx: watch::Receiver<Report>,
 }
 
 impl ComponentMonitor {
-    pub const fn new(component_tx: mpsc::WeakUnboundedSender<ComponentMessage>, health_rx: watch::Receiver<Health>) -> Self {
+    pub const fn new(component_tx: mpsc::WeakUnboundedSender<ComponentMessage>, health_rx: watch::Receiver<Report>) -> Self {
         Self { component_tx, health_rx }
     }
 
-    /// Get the overall health state of the component.
+    /// Get the most recently published summary of the component's health.
     ///
-    /// The overall health is determined by the most severe health state reported by any publisher.
+    /// The summary includes the component's state and signal counts, but no signals.
     #[must_use]
-    pub fn state(&self) -> Health {
-        *self.health_rx.borrow()
+    pub fn summary(&self) -> Report {
+        self.health_rx.borrow().clone()
+    }
+
+    /// Get a receiver that observes each published summary of the component's health.
+    pub fn subscribe(&self) -> watch::Receiver<Report> {
+        self.health_rx.clone()
+    }
+
+    /// Replace the component's upstream impact signals.
+    pub fn set_upstream_signals(&self, signals: Vec<Signal>) {
+        if let Some(channel) = self.component_tx.upgrade() {
+            let _ = channel.send(ComponentMessage::SetUpstreamSignals(signals));
+        }
+    }
+
+    /// Put the component into maintenance, or take it out of maintenance with `None`.
+    pub fn set_maintenance(&self, maintenance: Option<Maintenance>) {
+        if let Some(channel) = self.component_tx.upgrade() {
+            let _ = channel.send(ComponentMessage::SetMaintenance(maintenance));
+        }
     }
 
     pub fn alive(&self) -> bool {
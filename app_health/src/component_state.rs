@@ -1,31 +1,94 @@
+use crate::component_monitor::ComponentMonitor;
 use crate::health::{ALL_HEALTH_STATES, NUM_HEALTH_STATES};
+use crate::hysteresis::Hysteresis;
 use crate::signal::Signal;
-use crate::{Filter, Health, Report};
+use crate::{AggregationPolicy, Criticality, Escalation, Filter, Health, HealthOverride, Maintenance, Probes, Report, Runtime, Transition};
 use core::array::from_fn;
 use core::cell::Cell;
-use std::collections::HashMap;
+use core::future::pending;
+use core::time::Duration;
 use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
-/// A component's state, tracking the aggregate health of its publishers.
+/// A component's state, tracking the aggregate health of its publishers and children.
 #[derive(Debug)]
 pub struct ComponentState {
     name: Arc<str>,
+    criticality: Criticality,
+    probes: Probes,
     state: Cell<Option<Health>>,
+    since: SystemTime,
     counts: [usize; NUM_HEALTH_STATES],
     signals: [HashMap<Signal, usize>; NUM_HEALTH_STATES],
+    history: VecDeque<Transition>,
+    history_capacity: usize,
+    hysteresis: Hysteresis,
+    children: Vec<ComponentMonitor>,
+    upstream_signals: Vec<Signal>,
+    policy: Arc<dyn AggregationPolicy>,
+    maintenance: Option<(Maintenance, Option<Instant>)>,
+    health_override: Option<(HealthOverride, Option<Instant>)>,
+    published: bool,
+    startup_deadline: Option<Instant>,
+    runtime: Arc<dyn Runtime>,
 }
 
 impl ComponentState {
-    pub fn new(name: Arc<str>) -> Self {
+    pub fn new(
+        name: Arc<str>,
+        criticality: Criticality,
+        probes: Probes,
+        history_capacity: usize,
+        hysteresis: Hysteresis,
+        policy: Arc<dyn AggregationPolicy>,
+        runtime: Arc<dyn Runtime>,
+    ) -> Self {
         Self {
             name,
+            criticality,
+            probes,
             state: Cell::new(None),
+            since: SystemTime::now(),
             counts: [0; NUM_HEALTH_STATES],
             signals: from_fn(|_| HashMap::default()),
+            history: VecDeque::with_capacity(history_capacity),
+            history_capacity,
+            hysteresis,
+            children: Vec::new(),
+            upstream_signals: Vec::new(),
+            policy,
+            maintenance: None,
+            health_override: None,
+            published: false,
+            startup_deadline: None,
+            runtime,
         }
     }
 
+    /// Record a change in the component's state, evicting the oldest transition if the history is full.
+    pub fn record_transition(&mut self, old_state: Health, new_state: Health, signal: Option<Signal>) {
+        let timestamp = SystemTime::now();
+        self.since = timestamp;
+
+        if self.history_capacity == 0 {
+            return;
+        }
+
+        if self.history.len() == self.history_capacity {
+            let _ = self.history.pop_front();
+        }
+
+        self.history.push_back(Transition {
+            component: Some(Arc::clone(&self.name)),
+            timestamp,
+            old_state,
+            new_state,
+            signal,
+        });
+    }
+
     /// Incorporate a publisher's health into the component's aggregate state.
     pub fn add_publisher_signal(&mut self, signal: Signal) {
         // induce the state to be recomputed on query
@@ -33,7 +96,17 @@ impl ComponentState {
 
         let index = signal.state() as usize;
         self.counts[index] += 1;
-        *self.signals[index].entry(signal).or_insert(0) += 1;
+        inc_or_insert(&mut self.signals[index], signal);
+        self.refresh();
+    }
+
+    /// Note that a publisher has published the same signal again.
+    pub fn reaffirm_signal(&mut self, signal: &Signal) {
+        let signals = &mut self.signals[signal.state() as usize];
+        if let Some((mut key, count)) = signals.remove_entry(signal) {
+            key.reaffirm(signal.last_reaffirmed());
+            let _ = signals.insert(key, count);
+        }
     }
 
     /// Remove a publisher's health from the component's aggregate state.
@@ -44,10 +117,158 @@ impl ComponentState {
         let index = signal.state() as usize;
         self.counts[index] -= 1;
         dec_or_remove(&mut self.signals[index], signal);
+        self.refresh();
+    }
+
+    /// Start rolling up a child component's health into this component's state.
+    pub fn add_child(&mut self, child: ComponentMonitor) {
+        self.children.push(child);
+        self.refresh_children();
+    }
+
+    /// Re-evaluate the component's state after a change to one of its children, forgetting children that have been dropped.
+    pub fn refresh_children(&mut self) {
+        self.children.retain(ComponentMonitor::alive);
+
+        // induce the state to be recomputed on query
+        self.state.set(None);
+        self.refresh();
+    }
+
+    /// Replace the signals reflecting the impact of unhealthy dependencies, returning `true` if they changed.
+    pub fn set_upstream_signals(&mut self, signals: Vec<Signal>) -> bool {
+        if signals == self.upstream_signals {
+            return false;
+        }
+
+        for signal in core::mem::take(&mut self.upstream_signals) {
+            self.remove_publisher_signal(signal);
+        }
+
+        for signal in &signals {
+            self.add_publisher_signal(signal.clone());
+        }
+
+        self.upstream_signals = signals;
+        true
+    }
+
+    /// Start the component's startup grace period, which lasts until the given time has passed or a publisher publishes.
+    pub fn begin_startup(&mut self, grace_period: Duration) {
+        if !grace_period.is_zero() {
+            self.startup_deadline = self.runtime.now().checked_add(grace_period);
+        }
+    }
+
+    /// Returns a future that resolves when the component's startup grace period runs out.
+    pub fn startup_expired(&self) -> impl Future<Output = ()> + use<> {
+        self.sleep_until(self.startup_deadline)
+    }
+
+    /// End the component's startup grace period.
+    pub const fn end_startup(&mut self) {
+        self.startup_deadline = None;
+    }
+
+    /// Note that one of the component's publishers has published a signal, returning `true` if it's the first time.
+    ///
+    /// The first publication ends the component's startup grace period.
+    pub const fn record_publication(&mut self) -> bool {
+        if self.published {
+            return false;
+        }
+
+        self.published = true;
+        self.end_startup();
+        true
+    }
+
+    /// Put the component into maintenance, or take it out of maintenance with `None`.
+    pub fn set_maintenance(&mut self, maintenance: Option<Maintenance>) {
+        let now = self.runtime.now();
+        self.maintenance = maintenance.map(|mut maintenance| {
+            maintenance.started = SystemTime::now();
+            let deadline = now.checked_add(maintenance.duration());
+            (maintenance, deadline)
+        });
+    }
+
+    /// Returns a future that resolves when the component's maintenance runs out.
+    pub fn maintenance_expired(&self) -> impl Future<Output = ()> + use<> {
+        self.sleep_until(self.maintenance.as_ref().and_then(|(_, deadline)| *deadline))
+    }
+
+    /// The maintenance the component is in, if any.
+    pub fn maintenance(&self) -> Option<&Maintenance> {
+        self.maintenance.as_ref().map(|(maintenance, _)| maintenance)
+    }
+
+    /// Force the component's state, or go back to the state derived from its signals with `None`.
+    pub fn set_override(&mut self, health_override: Option<HealthOverride>) {
+        let now = self.runtime.now();
+        self.health_override = health_override.map(|mut health_override| {
+            health_override.started = SystemTime::now();
+            let deadline = now.checked_add(health_override.duration());
+            (health_override, deadline)
+        });
     }
 
-    /// Get the component's aggregate health state, computing and caching it if needed.
+    /// Returns a future that resolves when the override of the component's state runs out.
+    pub fn override_expired(&self) -> impl Future<Output = ()> + use<> {
+        self.sleep_until(self.health_override.as_ref().and_then(|(_, deadline)| *deadline))
+    }
+
+    /// The override of the component's state, if any.
+    pub fn health_override(&self) -> Option<&HealthOverride> {
+        self.health_override.as_ref().map(|(health_override, _)| health_override)
+    }
+
+    /// Returns a future that resolves once the deadline has passed, or never if there's no deadline.
+    fn sleep_until(&self, deadline: Option<Instant>) -> impl Future<Output = ()> + use<> {
+        let sleep = deadline.map(|deadline| self.runtime.sleep_until(deadline));
+
+        async move {
+            match sleep {
+                Some(sleep) => sleep.await,
+                None => pending().await,
+            }
+        }
+    }
+
+    /// The component's live children.
+    pub fn children(&self) -> &[ComponentMonitor] {
+        &self.children
+    }
+
+    /// Re-evaluate the component's stabilized state, which can change over time even without new signals.
+    pub fn refresh(&mut self) {
+        let signal_state = self.signal_state();
+        self.hysteresis.update(signal_state);
+    }
+
+    /// Returns a future that resolves when the component's state may change without any new signals.
+    pub fn expired(&self) -> impl Future<Output = ()> + use<> {
+        self.hysteresis.expired()
+    }
+
+    /// Get the component's health state, after stabilizing it over time, unless it's overridden.
     pub fn state(&self) -> Health {
+        self.health_override()
+            .map_or_else(|| self.hysteresis.state(), HealthOverride::state)
+    }
+
+    /// Whether the component's state has changed too often recently.
+    pub fn is_flapping(&self) -> bool {
+        self.hysteresis.is_flapping()
+    }
+
+    /// The escalation rule currently in effect, if any.
+    pub const fn escalation(&self) -> Option<Escalation> {
+        self.hysteresis.escalation()
+    }
+
+    /// Get the aggregate health state of the publisher signals and children, computing and caching it if needed.
+    fn signal_state(&self) -> Health {
         if let Some(state) = self.state.get() {
             return state;
         }
@@ -58,6 +279,13 @@ impl ComponentState {
             .find_map(|hs| (self.counts[*hs as usize] > 0).then_some(*hs))
             .unwrap_or_default();
 
+        let state = if self.children.is_empty() {
+            state
+        } else {
+            let summaries: Vec<Report> = self.children.iter().map(ComponentMonitor::summary).collect();
+            state.max(self.policy.aggregate(&summaries))
+        };
+
         self.state.set(Some(state));
         state
     }
@@ -68,7 +296,14 @@ impl ComponentState {
         Report {
             name: Arc::clone(&self.name),
             state,
+            criticality: self.criticality,
+            probes: self.probes,
+            flapping: self.is_flapping(),
+            escalation: self.escalation(),
+            since: self.since,
             counts: self.counts,
+            oldest_signals: from_fn(|i| self.signals[i].keys().map(Signal::first_published).min()),
+            newest_signals: from_fn(|i| self.signals[i].keys().map(Signal::first_published).max()),
             signals: from_fn(|i| {
                 let health_state_bit = 1 << i;
 
@@ -78,10 +313,34 @@ impl ComponentState {
                     Vec::new()
                 }
             }),
+            history: if filter.contains(Filter::HISTORY) {
+                self.history.iter().cloned().collect()
+            } else {
+                Vec::new()
+            },
+            children: Vec::new(),
+            maintenance: self.maintenance().cloned(),
+            health_override: self.health_override().cloned(),
+            starting: self.startup_deadline.is_some(),
+            published: self.published,
         }
     }
 }
 
+/// Increment a count for an individual key in the map, merging the key's timestamps into those of an existing equal key.
+fn inc_or_insert(map: &mut HashMap<Signal, usize>, key: Signal) {
+    // keys can't be modified in place, so an existing key is taken out and put back
+    let (key, count) = match map.remove_entry(&key) {
+        Some((mut existing, count)) => {
+            existing.merge(&key);
+            (existing, count)
+        }
+        None => (key, 0),
+    };
+
+    let _ = map.insert(key, count + 1);
+}
+
 /// Decrement a count for an individual key in the map, removing the key when the count reaches zero.
 fn dec_or_remove(map: &mut HashMap<Signal, usize>, key: Signal) {
     if let Entry::Occupied(mut e) = map.entry(key) {
@@ -0,0 +1,15 @@
+use crate::{Health, Report};
+
+/// A change in the health state of a component, broadcast by the aggregator worker.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    not(feature = "otel"),
+    expect(dead_code, reason = "only consumed by the OpenTelemetry integration")
+)]
+pub struct ComponentTransition {
+    /// The state of the component before the transition.
+    pub old_state: Health,
+
+    /// A summary of the component after the transition, including the signals in its new state.
+    pub report: Report,
+}
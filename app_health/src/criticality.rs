@@ -0,0 +1,63 @@
+use crate::Health;
+
+/// How much a component's health is allowed to affect the overall health of the application.
+///
+/// A component's criticality doesn't change the component's own health, which is still reported as-is
+/// in its [`Report`](crate::Report). Instead, it caps the health the component contributes when an aggregator
+/// computes the application's overall health. See [`Report::effective_state`](crate::Report::effective_state).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Criticality {
+    /// The component is essential to the application, so its health fully impacts overall health.
+    #[default]
+    Required,
+
+    /// The component is optional, so its impact on overall health is capped at the given health state.
+    ///
+    /// For example, `Optional(Health::Degraded)` means the component never makes the application
+    /// worse than [`Degraded`](Health::Degraded), even if the component itself is [`Down`](Health::Down).
+    Optional(Health),
+
+    /// The component is best-effort, so it never affects overall health.
+    BestEffort,
+}
+
+impl Criticality {
+    /// The most severe health state a component with this criticality can contribute to overall health.
+    #[must_use]
+    pub const fn ceiling(self) -> Health {
+        match self {
+            Self::Required => Health::Unrecoverable,
+            Self::Optional(ceiling) => ceiling,
+            Self::BestEffort => Health::Nominal,
+        }
+    }
+
+    /// Apply this criticality's ceiling to a component's health.
+    #[must_use]
+    pub fn cap(self, state: Health) -> Health {
+        state.min(self.ceiling())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_is_uncapped() {
+        assert_eq!(Criticality::Required.cap(Health::Unrecoverable), Health::Unrecoverable);
+    }
+
+    #[test]
+    fn optional_caps_at_ceiling() {
+        let criticality = Criticality::Optional(Health::Degraded);
+        assert_eq!(criticality.cap(Health::Down), Health::Degraded);
+        assert_eq!(criticality.cap(Health::Nominal), Health::Nominal);
+    }
+
+    #[test]
+    fn best_effort_is_always_nominal() {
+        assert_eq!(Criticality::BestEffort.cap(Health::Down), Health::Nominal);
+    }
+}
@@ -1,22 +1,29 @@
+use crate::Runtime;
+use core::future::pending;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 use core::time::Duration;
-use tokio::time::{Instant, Sleep, sleep};
+use std::sync::Arc;
+use std::time::Instant;
 
 pub struct Debouncer {
     debounce_delay: Duration,
+    runtime: Arc<dyn Runtime>,
     last_fired: Instant,
-    timer: Pin<Box<Sleep>>,
-    timer_active: bool,
+    timer: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
 }
 
 impl Debouncer {
-    pub fn new(debounce_delay: Duration) -> Self {
+    pub fn new(debounce_delay: Duration, runtime: Arc<dyn Runtime>) -> Self {
         Self {
-            debounce_delay,
-            last_fired: Instant::now(),
-            timer: Box::pin(sleep(Duration::from_secs(0))),
-            timer_active: false,
+            debounce_delay: if runtime.supports_debouncing() {
+                debounce_delay
+            } else {
+                Duration::ZERO
+            },
+            last_fired: runtime.now(),
+            runtime,
+            timer: None,
         }
     }
 
@@ -25,19 +32,23 @@ impl Debouncer {
     /// Returns `true` if the event should be processed immediately,
     /// or `false` if it should be deferred until the debounce period.
     pub fn trigger(&mut self) -> bool {
-        let now = Instant::now();
+        let now = self.runtime.now();
         let elapsed = now.duration_since(self.last_fired);
         if elapsed >= self.debounce_delay {
             // Immediate update; cancel any pending timer.
-            self.timer_active = false;
+            self.timer = None;
             self.last_fired = now;
             true
         } else {
-            if !self.timer_active {
+            if self.timer.is_none() {
                 // Schedule the debounce timer
-                let delay = self.debounce_delay - elapsed;
-                self.timer.as_mut().reset(now + delay);
-                self.timer_active = true;
+                let delay = self.debounce_delay.saturating_sub(elapsed);
+
+                // a delay too long to represent never runs out
+                self.timer = Some(match now.checked_add(delay) {
+                    Some(deadline) => self.runtime.sleep_until(deadline),
+                    None => Box::pin(pending()),
+                });
             }
 
             false
@@ -59,19 +70,49 @@ impl Future for DebounceReady<'_> {
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         // Only poll the timer if it's active
-        if !self.debouncer.timer_active {
+        let Some(timer) = self.debouncer.timer.as_mut() else {
             return Poll::Pending;
-        }
+        };
 
         // Poll the timer
-        match self.debouncer.timer.as_mut().poll(cx) {
+        match timer.as_mut().poll(cx) {
             Poll::Ready(()) => {
                 // Timer fired and was active, so we can complete
-                self.debouncer.timer_active = false;
-                self.debouncer.last_fired = Instant::now();
+                self.debouncer.timer = None;
+                self.debouncer.last_fired = self.debouncer.runtime.now();
                 Poll::Ready(())
             }
             Poll::Pending => Poll::Pending,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TokioRuntime;
+    use tokio::time::timeout;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[tokio::test(start_paused = true)]
+    async fn events_are_deferred_until_the_delay_has_passed() {
+        let mut debouncer = Debouncer::new(SECOND, Arc::new(TokioRuntime::new()));
+
+        assert!(!debouncer.trigger());
+        assert!(!debouncer.trigger());
+        assert!(timeout(SECOND / 2, debouncer.ready()).await.is_err());
+
+        debouncer.ready().await;
+        assert!(!debouncer.trigger());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn huge_debounce_interval() {
+        let mut debouncer = Debouncer::new(Duration::MAX, Arc::new(TokioRuntime::new()));
+
+        assert!(!debouncer.trigger());
+        assert!(timeout(SECOND * 60, debouncer.ready()).await.is_err());
+        assert!(!debouncer.trigger());
+    }
+}
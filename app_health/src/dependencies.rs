@@ -0,0 +1,134 @@
+use crate::component_monitor::ComponentMonitor;
+use crate::signal::Signal;
+use crate::{Attribute, DependencyCycle, DependencyGraph, Health};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+/// The dependencies declared between components, kept by the aggregator worker.
+///
+/// A component whose dependency reaches the impact threshold receives an upstream impact signal naming
+/// the dependency, on top of the signals from its own publishers.
+#[derive(Debug)]
+pub struct Dependencies {
+    edges: BTreeMap<Arc<str>, BTreeSet<Arc<str>>>,
+    threshold: Health,
+    state: Health,
+}
+
+impl Dependencies {
+    pub const fn new(threshold: Health, state: Health) -> Self {
+        Self {
+            edges: BTreeMap::new(),
+            threshold,
+            state,
+        }
+    }
+
+    /// Declare that `dependent` depends on `dependency`, unless doing so would create a cycle.
+    pub fn add(&mut self, dependent: Arc<str>, dependency: Arc<str>) -> Result<(), DependencyCycle> {
+        if let Some(mut path) = self.path(&dependency, &dependent, &mut HashSet::new()) {
+            path.insert(0, dependent);
+            return Err(DependencyCycle::new(path));
+        }
+
+        let _ = self.edges.entry(dependent).or_default().insert(dependency);
+        Ok(())
+    }
+
+    /// Find a path of dependencies leading from one component to another.
+    fn path<'a>(&'a self, from: &'a Arc<str>, to: &Arc<str>, visited: &mut HashSet<&'a Arc<str>>) -> Option<Vec<Arc<str>>> {
+        if from == to {
+            return Some(vec![Arc::clone(to)]);
+        }
+
+        if !visited.insert(from) {
+            return None;
+        }
+
+        self.edges.get(from)?.iter().find_map(|next| {
+            let mut path = self.path(next, to, visited)?;
+            path.insert(0, Arc::clone(from));
+            Some(path)
+        })
+    }
+
+    /// Send each live component the upstream impact signals matching the current state of its dependencies.
+    ///
+    /// Components ignore updates that don't change their set of impact signals, so this can be called freely.
+    pub fn propagate(&self, monitors: &[ComponentMonitor]) {
+        if self.edges.is_empty() {
+            return;
+        }
+
+        let states = states(monitors);
+        for monitor in monitors.iter().filter(|m| m.alive()) {
+            let summary = monitor.summary();
+            let signals = self
+                .edges
+                .get(summary.name())
+                .into_iter()
+                .flatten()
+                .filter(|dependency| states.get(dependency.as_ref()).is_some_and(|state| *state >= self.threshold))
+                .map(|dependency| {
+                    Signal::new(
+                        self.state,
+                        [
+                            Attribute::from(("upstream_impacted", true)),
+                            Attribute::from(("dependency", dependency.to_string())),
+                        ],
+                    )
+                })
+                .collect();
+
+            monitor.set_upstream_signals(signals);
+        }
+    }
+
+    /// Take a snapshot of the dependencies along with the current state of each component.
+    pub fn graph(&self, monitors: &[ComponentMonitor]) -> DependencyGraph {
+        let states = states(monitors);
+        let mut nodes: BTreeMap<Arc<str>, Option<Health>> = states.into_iter().map(|(name, state)| (name, Some(state))).collect();
+
+        let mut edges = Vec::new();
+        for (dependent, dependencies) in &self.edges {
+            for dependency in dependencies {
+                let _ = nodes.entry(Arc::clone(dependent)).or_default();
+                let _ = nodes.entry(Arc::clone(dependency)).or_default();
+                edges.push((Arc::clone(dependent), Arc::clone(dependency)));
+            }
+        }
+
+        DependencyGraph::new(nodes.into_iter().collect(), edges)
+    }
+}
+
+/// Get the state of each live component by name, keeping the worst state when several components share a name.
+fn states(monitors: &[ComponentMonitor]) -> HashMap<Arc<str>, Health> {
+    let mut states: HashMap<Arc<str>, Health> = HashMap::new();
+    for summary in monitors.iter().filter(|m| m.alive()).map(ComponentMonitor::summary) {
+        let state = states.entry(Arc::clone(&summary.name)).or_default();
+        *state = (*state).max(summary.state());
+    }
+
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_are_rejected() {
+        let mut dependencies = Dependencies::new(Health::Down, Health::Degraded);
+        dependencies.add("api".into(), "database".into()).unwrap();
+        dependencies.add("database".into(), "storage".into()).unwrap();
+        dependencies.add("api".into(), "storage".into()).unwrap();
+
+        let cycle = dependencies.add("storage".into(), "api".into()).unwrap_err();
+        assert_eq!(cycle.path().collect::<Vec<_>>(), ["storage", "api", "database", "storage"]);
+        assert_eq!(cycle.to_string(), "dependency cycle: storage -> api -> database -> storage");
+
+        let cycle = dependencies.add("api".into(), "api".into()).unwrap_err();
+        assert_eq!(cycle.path().collect::<Vec<_>>(), ["api", "api"]);
+    }
+}
@@ -0,0 +1,39 @@
+use core::fmt::Display;
+use std::sync::Arc;
+
+/// The error returned when declaring a dependency would create a cycle between components.
+///
+/// Dependencies are declared with [`Aggregator::add_dependency`](crate::Aggregator::add_dependency).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCycle {
+    path: Vec<Arc<str>>,
+}
+
+impl DependencyCycle {
+    pub(crate) const fn new(path: Vec<Arc<str>>) -> Self {
+        Self { path }
+    }
+
+    /// The names of the components forming the cycle, starting and ending with the same component.
+    #[must_use]
+    pub fn path(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.path.iter().map(AsRef::as_ref)
+    }
+}
+
+impl Display for DependencyCycle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "dependency cycle: ")?;
+        for (i, name) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+
+            write!(f, "{name}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for DependencyCycle {}
@@ -0,0 +1,88 @@
+use crate::{Health, flowchart};
+use std::sync::Arc;
+
+/// A snapshot of the dependencies declared between components, along with the health of each component.
+///
+/// Snapshots are taken with [`Aggregator::dependency_graph`](crate::Aggregator::dependency_graph).
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::Aggregator;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let _api = aggregator.component("api");
+///     let _database = aggregator.component("database");
+///
+///     aggregator.add_dependency("api", "database").await.unwrap();
+///
+///     if let Some(graph) = aggregator.dependency_graph().await {
+///         println!("{}", graph.to_mermaid());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyGraph {
+    nodes: Vec<(Arc<str>, Option<Health>)>,
+    edges: Vec<(Arc<str>, Arc<str>)>,
+}
+
+impl DependencyGraph {
+    pub(crate) const fn new(nodes: Vec<(Arc<str>, Option<Health>)>, edges: Vec<(Arc<str>, Arc<str>)>) -> Self {
+        Self { nodes, edges }
+    }
+
+    /// The names of the components in the graph, sorted by name.
+    ///
+    /// This includes every live component, along with any component named in a dependency even if it doesn't exist.
+    #[must_use]
+    pub fn components(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.nodes.iter().map(|(name, _)| name.as_ref())
+    }
+
+    /// The dependencies between components, as pairs of dependent and dependency names.
+    #[must_use]
+    pub fn dependencies(&self) -> impl ExactSizeIterator<Item = (&str, &str)> {
+        self.edges
+            .iter()
+            .map(|(dependent, dependency)| (dependent.as_ref(), dependency.as_ref()))
+    }
+
+    /// The health of a component at the time of the snapshot.
+    ///
+    /// This returns `None` if there was no live component with the given name. When several components share a name,
+    /// this is the worst of their states.
+    #[must_use]
+    pub fn state(&self, component: &str) -> Option<Health> {
+        self.nodes
+            .iter()
+            .find(|(name, _)| name.as_ref() == component)
+            .and_then(|(_, state)| *state)
+    }
+
+    /// Render the graph as a Mermaid flowchart, with each component colored according to its health.
+    ///
+    /// Arrows point from a component to the components it depends on. Components that don't exist are shown in gray.
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        let mut out = flowchart::start();
+        let id = |name: &str| self.nodes.iter().position(|(n, _)| n.as_ref() == name).unwrap_or_default();
+
+        for (i, (name, state)) in self.nodes.iter().enumerate() {
+            let label = state.map_or_else(|| "Unknown".to_string(), |s| s.to_string());
+            flowchart::node(&mut out, i, &[name, &label]);
+        }
+
+        for (dependent, dependency) in &self.edges {
+            flowchart::edge(&mut out, id(dependent), id(dependency));
+        }
+
+        for (i, (_, state)) in self.nodes.iter().enumerate() {
+            flowchart::style(&mut out, i, *state);
+        }
+
+        out
+    }
+}
@@ -0,0 +1,118 @@
+use crate::blocking::Blocking;
+use crate::health_check::{grade, saturating_i64};
+use crate::{Attribute, Health, HealthCheck};
+use core::cmp::Reverse;
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+/// Checks the amount of free space on the file system holding a path.
+///
+/// The check reports `Critical` if the free space can't be determined. Otherwise, the space available to
+/// unprivileged users is compared against the thresholds. The file system is queried on a separate thread, so that a hung
+/// mount results in a timeout rather than stalling the application. The check produces the following attributes:
+///
+/// - `path`: The path being checked.
+/// - `free_bytes`: The number of bytes available, when the free space can be determined.
+/// - `total_bytes`: The size of the file system, when the free space can be determined.
+/// - `error`: The reason the free space couldn't be determined, when it fails.
+///
+/// This check is only available on Unix platforms.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, DiskSpaceCheck};
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let storage = aggregator.component("storage");
+///
+///     storage.health_check(DiskSpaceCheck::new("/var/lib/my_app", 10 << 30, 1 << 30)).start();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiskSpaceCheck {
+    path: PathBuf,
+    degraded_below: u64,
+    critical_below: u64,
+    blocking: Blocking,
+}
+
+impl DiskSpaceCheck {
+    /// Create a check reporting `Degraded` once the free space drops to `degraded_below` bytes,
+    /// and `Critical` once it drops to `critical_below` bytes.
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>, degraded_below: u64, critical_below: u64) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            degraded_below,
+            critical_below,
+            blocking: Blocking::default(),
+        }
+    }
+}
+
+impl HealthCheck for DiskSpaceCheck {
+    async fn check(&self) -> (Health, Vec<Attribute>) {
+        let path = Attribute::from(("path", self.path.display().to_string()));
+
+        let file_system = self.path.clone();
+        match self.blocking.run(move || space(&file_system)).await {
+            Ok((free, total)) => (
+                grade(Reverse(free), Reverse(self.degraded_below), Reverse(self.critical_below)),
+                vec![
+                    path,
+                    ("free_bytes", saturating_i64(free)).into(),
+                    ("total_bytes", saturating_i64(total)).into(),
+                ],
+            ),
+
+            Err(e) => (Health::Critical, vec![path, ("error", e.to_string()).into()]),
+        }
+    }
+}
+
+/// Get the free and total bytes of the file system holding the given path.
+#[allow(trivial_numeric_casts, clippy::cast_lossless, reason = "field types vary across platforms")]
+fn space(path: &Path) -> io::Result<(u64, u64)> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+
+    // SAFETY: statvfs is a plain C struct, for which all-zero is a valid value
+    let mut stats: libc::statvfs = unsafe { core::mem::zeroed() };
+
+    // SAFETY: the path is a valid NUL-terminated string and the stats pointer is valid for writes
+    if unsafe { libc::statvfs(path.as_ptr(), &raw mut stats) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let block_size = stats.f_frsize as u64;
+    Ok((
+        (stats.f_bavail as u64).saturating_mul(block_size),
+        (stats.f_blocks as u64).saturating_mul(block_size),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn measures_temp_dir() {
+        let (state, attributes) = DiskSpaceCheck::new(std::env::temp_dir(), 0, 0).check().await;
+        assert_eq!(state, Health::Nominal);
+        assert!(attributes.iter().any(|a| a.name().as_str() == "free_bytes"));
+
+        let (state, _) = DiskSpaceCheck::new(std::env::temp_dir(), u64::MAX, u64::MAX).check().await;
+        assert_eq!(state, Health::Critical);
+    }
+
+    #[tokio::test]
+    async fn reports_missing_paths() {
+        let (state, attributes) = DiskSpaceCheck::new("/does/not/exist", 0, 0).check().await;
+        assert_eq!(state, Health::Critical);
+        assert!(attributes.iter().any(|a| a.name().as_str() == "error"));
+    }
+}
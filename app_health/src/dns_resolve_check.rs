@@ -0,0 +1,116 @@
+use crate::health_check::{grade, saturating_i64};
+use crate::{Attribute, Health, HealthCheck};
+use core::time::Duration;
+use tokio::net::lookup_host;
+use tokio::time::Instant;
+
+/// Checks that a host name resolves to at least one address.
+///
+/// The check reports `Critical` if resolution fails or yields no addresses. Otherwise, the time taken to
+/// resolve is compared against the latency thresholds. The check produces the following attributes:
+///
+/// - `host`: The host name being resolved.
+/// - `addresses`: The number of addresses the host resolved to, when resolution succeeds.
+/// - `latency_ms`: The time taken to resolve, when resolution succeeds.
+/// - `error`: The reason resolution failed, when it fails.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, DnsResolveCheck};
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let network = aggregator.component("network");
+///
+///     network.health_check(DnsResolveCheck::new("example.com")).start();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DnsResolveCheck {
+    host: Box<str>,
+    degraded_latency: Duration,
+    critical_latency: Duration,
+}
+
+impl DnsResolveCheck {
+    /// Create a check that resolves the given host name.
+    #[must_use]
+    pub fn new(host: impl AsRef<str>) -> Self {
+        Self {
+            host: host.as_ref().into(),
+            degraded_latency: Duration::from_millis(250),
+            critical_latency: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the resolution latency at which the check reports `Degraded`.
+    ///
+    /// Defaults to 250 milliseconds.
+    #[must_use]
+    pub const fn degraded_latency(mut self, latency: Duration) -> Self {
+        self.degraded_latency = latency;
+        self
+    }
+
+    /// Set the resolution latency at which the check reports `Critical`.
+    ///
+    /// Defaults to one second.
+    #[must_use]
+    pub const fn critical_latency(mut self, latency: Duration) -> Self {
+        self.critical_latency = latency;
+        self
+    }
+}
+
+impl HealthCheck for DnsResolveCheck {
+    async fn check(&self) -> (Health, Vec<Attribute>) {
+        let host = Attribute::from(("host", self.host.clone()));
+
+        let start = Instant::now();
+        match lookup_host((&*self.host, 0)).await {
+            Ok(addresses) => {
+                let latency = start.elapsed();
+                let count = addresses.count();
+                let state = if count == 0 {
+                    Health::Critical
+                } else {
+                    grade(latency, self.degraded_latency, self.critical_latency)
+                };
+
+                (
+                    state,
+                    vec![
+                        host,
+                        ("addresses", saturating_i64(count)).into(),
+                        ("latency_ms", saturating_i64(latency.as_millis())).into(),
+                    ],
+                )
+            }
+
+            Err(e) => (Health::Critical, vec![host, ("error", e.to_string()).into()]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn resolves_localhost() {
+        let (state, attributes) = DnsResolveCheck::new("localhost").check().await;
+        assert_eq!(state, Health::Nominal);
+
+        let addresses = attributes.iter().find(|a| a.name().as_str() == "addresses").unwrap();
+        assert_ne!(addresses.value(), &0_i64.into());
+    }
+
+    #[tokio::test]
+    async fn reports_unresolvable_hosts() {
+        let (state, attributes) = DnsResolveCheck::new("invalid host name").check().await;
+        assert_eq!(state, Health::Critical);
+        assert!(attributes.iter().any(|a| a.name().as_str() == "error"));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::sync::mpsc;
+use core::convert::Infallible;
+
+/// Held by components and publishers to keep an aggregator that's shutting down from considering itself drained.
+///
+/// Nothing is ever sent over the channel. The aggregator holds the only other sender until shutdown begins, after which
+/// its receiver completes once the last guard has been dropped.
+#[derive(Debug, Clone, Default)]
+pub struct DrainGuard {
+    _drain_tx: Option<mpsc::UnboundedSender<Infallible>>,
+}
+
+impl DrainGuard {
+    /// Create a guard for the aggregator that owns the channel, if it isn't drained already.
+    pub fn new(drain_tx: &mpsc::WeakUnboundedSender<Infallible>) -> Self {
+        Self {
+            _drain_tx: drain_tx.upgrade(),
+        }
+    }
+}
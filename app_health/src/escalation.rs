@@ -0,0 +1,55 @@
+use crate::Health;
+use core::time::Duration;
+
+/// A rule that escalates a component which stays in a given state for too long.
+///
+/// A component that has been in the [`from`](Self::from) state continuously for the [`after`](Self::after)
+/// duration is reported in the next worse state instead. For example, a component that has been `Degraded`
+/// for five minutes can be considered `Critical`.
+///
+/// Rules are added with [`ComponentBuilder::escalate`](crate::ComponentBuilder::escalate), and the rule
+/// currently in effect for a component is available with [`Report::escalation`](crate::Report::escalation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Escalation {
+    from: Health,
+    after: Duration,
+}
+
+impl Escalation {
+    /// Create a rule that escalates a component after it has been in the `from` state for the `after` duration.
+    #[must_use]
+    pub const fn new(from: Health, after: Duration) -> Self {
+        Self { from, after }
+    }
+
+    /// The state that triggers the rule.
+    #[must_use]
+    pub const fn from(&self) -> Health {
+        self.from
+    }
+
+    /// How long the component must remain in the [`from`](Self::from) state before the rule fires.
+    #[must_use]
+    pub const fn after(&self) -> Duration {
+        self.after
+    }
+
+    /// The state the component is reported in once the rule fires.
+    #[must_use]
+    pub const fn to(&self) -> Health {
+        self.from.worse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escalates_one_step() {
+        assert_eq!(Escalation::new(Health::Degraded, Duration::ZERO).to(), Health::Critical);
+        assert_eq!(Escalation::new(Health::Down, Duration::ZERO).to(), Health::Unrecoverable);
+        assert_eq!(Escalation::new(Health::Unrecoverable, Duration::ZERO).to(), Health::Unrecoverable);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::blocking::Blocking;
+use crate::health_check::{grade, saturating_i64};
+use crate::{Attribute, Health, HealthCheck};
+use core::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Checks that a file has been modified recently.
+///
+/// This is useful to detect when a file that's expected to be refreshed regularly, such as configuration
+/// written by a sidecar, has gone stale. The check reports `Critical` if the file's modification time can't
+/// be read. Otherwise, the file's age is compared against the thresholds. The file system is queried on a separate thread,
+/// so that a hung mount results in a timeout rather than stalling the application. The check produces the following
+/// attributes:
+///
+/// - `path`: The path being checked.
+/// - `age_ms`: The time elapsed since the file was last modified, when it can be determined.
+/// - `error`: The reason the modification time couldn't be read, when it fails.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, FileFreshnessCheck};
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let config = aggregator.component("config");
+///
+///     let check = FileFreshnessCheck::new("/etc/my_app/config.json").degraded_after(Duration::from_secs(60));
+///     config.health_check(check).start();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileFreshnessCheck {
+    path: PathBuf,
+    degraded_after: Duration,
+    critical_after: Duration,
+    blocking: Blocking,
+}
+
+impl FileFreshnessCheck {
+    /// Create a check for the given file.
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            degraded_after: Duration::from_secs(5 * 60),
+            critical_after: Duration::from_secs(60 * 60),
+            blocking: Blocking::default(),
+        }
+    }
+
+    /// Set the age at which the check reports `Degraded`.
+    ///
+    /// Defaults to five minutes.
+    #[must_use]
+    pub const fn degraded_after(mut self, age: Duration) -> Self {
+        self.degraded_after = age;
+        self
+    }
+
+    /// Set the age at which the check reports `Critical`.
+    ///
+    /// Defaults to one hour.
+    #[must_use]
+    pub const fn critical_after(mut self, age: Duration) -> Self {
+        self.critical_after = age;
+        self
+    }
+}
+
+impl HealthCheck for FileFreshnessCheck {
+    async fn check(&self) -> (Health, Vec<Attribute>) {
+        let path = Attribute::from(("path", self.path.display().to_string()));
+
+        let file = self.path.clone();
+        match self.blocking.run(move || file.metadata()?.modified()).await {
+            Ok(modified) => {
+                // a modification time in the future counts as fresh
+                let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+                (
+                    grade(age, self.degraded_after, self.critical_after),
+                    vec![path, ("age_ms", saturating_i64(age.as_millis())).into()],
+                )
+            }
+
+            Err(e) => (Health::Critical, vec![path, ("error", e.to_string()).into()]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn grades_file_age() {
+        let path = std::env::temp_dir().join(format!("app_health_freshness_{}", std::process::id()));
+        fs::write(&path, "{}").unwrap();
+
+        let (state, attributes) = FileFreshnessCheck::new(&path).check().await;
+        assert_eq!(state, Health::Nominal);
+        assert!(attributes.iter().any(|a| a.name().as_str() == "age_ms"));
+
+        let (state, _) = FileFreshnessCheck::new(&path).degraded_after(Duration::ZERO).check().await;
+        assert_eq!(state, Health::Degraded);
+
+        fs::remove_file(&path).unwrap();
+
+        let (state, attributes) = FileFreshnessCheck::new(&path).check().await;
+        assert_eq!(state, Health::Critical);
+        assert!(attributes.iter().any(|a| a.name().as_str() == "error"));
+    }
+}
@@ -23,5 +23,15 @@ bitflags! {
 
         /// Include all publisher signals.
         const ALL = Self::NOMINAL.bits() | Self::DEGRADED.bits() | Self::CRITICAL.bits() | Self::DOWN.bits() | Self::UNRECOVERABLE.bits();
+
+        /// Include the component's recent state transitions.
+        const HISTORY = 1 << 5;
+    }
+}
+
+impl Filter {
+    /// A filter that only includes publisher signals with the given health state.
+    pub(crate) const fn only(state: Health) -> Self {
+        Self::from_bits_retain(1 << state as u32)
     }
 }
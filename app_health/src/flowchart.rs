@@ -0,0 +1,74 @@
+//! Mermaid flowchart rendering shared by [`Reports`](crate::Reports) and [`DependencyGraph`](crate::DependencyGraph), so
+//! that both draw and escape components the same way.
+
+use crate::Health;
+use core::fmt::Write;
+
+/// The color of components whose health is unknown.
+const UNKNOWN_COLOR: &str = "#9e9e9e";
+
+/// Start a flowchart that flows from left to right.
+pub fn start() -> String {
+    String::from("flowchart LR\n")
+}
+
+/// Add a component's node, labeled with the given lines of text.
+pub fn node(out: &mut String, id: usize, lines: &[&str]) {
+    let label: Vec<String> = lines.iter().map(|line| escape(line)).collect();
+    let _ = writeln!(out, "    c{id}[\"{}\"]", label.join("<br/>"));
+}
+
+/// Add an arrow between two nodes.
+pub fn edge(out: &mut String, from: usize, to: usize) {
+    let _ = writeln!(out, "    c{from} --> c{to}");
+}
+
+/// Color a node according to the health of its component, or in gray if that's unknown.
+pub fn style(out: &mut String, id: usize, state: Option<Health>) {
+    let color = state.map_or(UNKNOWN_COLOR, Health::color);
+    let _ = writeln!(out, "    style c{id} fill:{color},color:black");
+}
+
+/// Escape text for use within a quoted label, using Mermaid's entity codes.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '#' => result.push_str("#35;"),
+            '"' => result.push_str("#quot;"),
+            '&' => result.push_str("#amp;"),
+            '<' => result.push_str("#lt;"),
+            '>' => result.push_str("#gt;"),
+            '\n' | '\r' => result.push(' '),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_labels() {
+        let mut out = start();
+        node(&mut out, 0, &["\"a\" <b>#1</b> & co", "line\nbreak"]);
+        assert_eq!(
+            out,
+            "flowchart LR\n    c0[\"#quot;a#quot; #lt;b#gt;#35;1#lt;/b#gt; #amp; co<br/>line break\"]\n"
+        );
+    }
+
+    #[test]
+    fn styles_unknown_components_in_gray() {
+        let mut out = String::new();
+        style(&mut out, 1, None);
+        style(&mut out, 2, Some(Health::Down));
+        assert_eq!(
+            out,
+            "    style c1 fill:#9e9e9e,color:black\n    style c2 fill:#f44336,color:black\n"
+        );
+    }
+}
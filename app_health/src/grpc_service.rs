@@ -0,0 +1,258 @@
+use crate::runtime::drive_stream;
+use crate::{Aggregator, Health};
+use core::future::ready;
+use core::pin::Pin;
+use futures_util::stream::{self, Stream, StreamExt};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_server::{self, HealthServer};
+use tonic_health::pb::{HealthCheckRequest, HealthCheckResponse};
+
+type WatchResult = Result<HealthCheckResponse, Status>;
+type HealthStream = Pin<Box<dyn Stream<Item = Health> + Send>>;
+
+/// Implements the standard [gRPC Health Checking Protocol](https://github.com/grpc/grpc/blob/master/doc/health-checking.md)
+/// (`grpc.health.v1.Health`) on top of an aggregator.
+///
+/// The empty service name refers to the overall health of the application, as returned by [`Aggregator::state`]. Any other
+/// service name refers to the component with that name, as returned by [`Component::state`](crate::Component::state).
+///
+/// Health states map onto serving statuses as follows:
+///
+/// - States less severe than the configured [`not_serving_threshold`](Self::not_serving_threshold) map to `SERVING`.
+/// - States at or beyond the threshold map to `NOT_SERVING`.
+/// - The empty service name maps to `UNKNOWN` once the aggregator's worker has stopped.
+/// - A service name that doesn't match any live component fails `Check` with `NOT_FOUND` and produces `SERVICE_UNKNOWN` from `Watch`.
+///
+/// `Watch` streams are driven by the same notifications as [`Aggregator::changed`] and
+/// [`Component::changed`](crate::Component::changed), and only produce a message when the serving status changes.
+/// If a watched component is dropped, the stream produces `SERVICE_UNKNOWN` and then stays open as required by the
+/// protocol, but it doesn't notice if a component with the same name is created later.
+///
+/// # Example
+///
+/// ```no_run
+/// use app_health::{Aggregator, GrpcHealthService};
+/// use std::sync::Arc;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Arc::new(Aggregator::new());
+///     let _database = aggregator.component("database");
+///
+///     let _server = GrpcHealthService::new(aggregator).into_server();
+///
+///     // add `_server` to a `tonic::transport::Server`...
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrpcHealthService {
+    aggregator: Arc<Aggregator>,
+    not_serving_threshold: Health,
+}
+
+impl GrpcHealthService {
+    /// Create a service for the given aggregator.
+    #[must_use]
+    pub const fn new(aggregator: Arc<Aggregator>) -> Self {
+        Self {
+            aggregator,
+            not_serving_threshold: Health::Critical,
+        }
+    }
+
+    /// Set the health state at which a service is reported as `NOT_SERVING`.
+    ///
+    /// Defaults to [`Health::Critical`].
+    #[must_use]
+    pub const fn not_serving_threshold(mut self, threshold: Health) -> Self {
+        self.not_serving_threshold = threshold;
+        self
+    }
+
+    /// Wrap the service in a server that can be added to a `tonic` router.
+    #[must_use]
+    pub fn into_server(self) -> HealthServer<Self> {
+        HealthServer::new(self)
+    }
+
+    /// Map a health state onto a serving status.
+    fn serving_status(&self, state: Health) -> ServingStatus {
+        if state >= self.not_serving_threshold {
+            ServingStatus::NotServing
+        } else {
+            ServingStatus::Serving
+        }
+    }
+
+    /// Get a stream of the health of the given service, or `None` if there's no such service.
+    ///
+    /// The stream produces the current health immediately, and ends once the service goes away.
+    async fn find(&self, service: &str) -> Option<HealthStream> {
+        let runtime = Arc::clone(self.aggregator.runtime());
+        if service.is_empty() {
+            return Some(Box::pin(drive_stream(runtime, self.aggregator.subscribe().into_stream())));
+        }
+
+        let report_rx = self.aggregator.find_component(service).await?;
+        Some(Box::pin(drive_stream(
+            runtime,
+            report_rx.into_stream().map(|report| report.state()),
+        )))
+    }
+}
+
+#[tonic::async_trait]
+impl health_server::Health for GrpcHealthService {
+    async fn check(&self, request: Request<HealthCheckRequest>) -> Result<Response<HealthCheckResponse>, Status> {
+        let service = request.into_inner().service;
+
+        let status = if service.is_empty() {
+            if self.aggregator.is_running() {
+                self.serving_status(self.aggregator.state())
+            } else {
+                ServingStatus::Unknown
+            }
+        } else {
+            let Some(report_rx) = self.aggregator.find_component(&service).await else {
+                return Err(Status::not_found(format!("unknown service: {service}")));
+            };
+
+            self.serving_status(report_rx.borrow().state())
+        };
+
+        Ok(Response::new(response(status)))
+    }
+
+    type WatchStream = Pin<Box<dyn Stream<Item = WatchResult> + Send>>;
+
+    async fn watch(&self, request: Request<HealthCheckRequest>) -> Result<Response<Self::WatchStream>, Status> {
+        let service = request.into_inner().service;
+        let gone = if service.is_empty() {
+            ServingStatus::Unknown
+        } else {
+            ServingStatus::ServiceUnknown
+        };
+
+        let Some(states) = self.find(&service).await else {
+            let stream = stream::once(ready(Ok(response(gone)))).chain(stream::pending());
+            return Ok(Response::new(Box::pin(stream)));
+        };
+
+        let this = self.clone();
+        let mut last = None;
+        let stream = states
+            .map(move |state| this.serving_status(state))
+            .chain(stream::once(ready(gone)))
+            .filter(move |status| ready(last.replace(*status) != Some(*status)))
+            .map(|status| Ok(response(status)))
+            .chain(stream::pending());
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+fn response(status: ServingStatus) -> HealthCheckResponse {
+    HealthCheckResponse { status: status.into() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use tokio::time::timeout;
+    use tonic_health::pb::health_server::Health as _;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    fn request(service: &str) -> Request<HealthCheckRequest> {
+        Request::new(HealthCheckRequest { service: service.into() })
+    }
+
+    fn aggregator() -> Arc<Aggregator> {
+        Arc::new(
+            Aggregator::builder()
+                .debounce_interval(Duration::ZERO)
+                .component_debounce_interval(Duration::ZERO)
+                .build(),
+        )
+    }
+
+    #[tokio::test]
+    async fn check_overall_and_component() {
+        let aggregator = aggregator();
+        let mut component = aggregator.component("database");
+        let service = GrpcHealthService::new(Arc::clone(&aggregator));
+
+        let status = service.check(request("")).await.unwrap().into_inner().status;
+        assert_eq!(status, ServingStatus::Serving as i32);
+
+        let mut publisher = component.publisher();
+        publisher.publish(Health::Down, [("reason", "unreachable")]);
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+
+        let status = service.check(request("database")).await.unwrap().into_inner().status;
+        assert_eq!(status, ServingStatus::NotServing as i32);
+
+        let error = service.check(request("missing")).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn watch_streams_changes() {
+        let aggregator = aggregator();
+        let component = aggregator.component("database");
+        let service = GrpcHealthService::new(Arc::clone(&aggregator));
+
+        let mut stream = service.watch(request("database")).await.unwrap().into_inner();
+        let first = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(first.status, ServingStatus::Serving as i32);
+
+        let mut publisher = component.publisher();
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+        let next = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(next.status, ServingStatus::NotServing as i32);
+
+        drop(publisher);
+        let next = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(next.status, ServingStatus::Serving as i32);
+
+        drop(component);
+        let last = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(last.status, ServingStatus::ServiceUnknown as i32);
+    }
+
+    #[tokio::test]
+    async fn watch_unknown_service() {
+        let service = GrpcHealthService::new(aggregator());
+
+        let mut stream = service.watch(request("missing")).await.unwrap().into_inner();
+        let first = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(first.status, ServingStatus::ServiceUnknown as i32);
+    }
+
+    #[cfg(feature = "inline")]
+    #[tokio::test]
+    async fn works_with_inline_runtime() {
+        let aggregator = Arc::new(Aggregator::builder().runtime(crate::InlineRuntime::new()).build());
+        let component = aggregator.component("database");
+        let service = GrpcHealthService::new(Arc::clone(&aggregator));
+
+        let response = timeout(WAIT, service.check(request("database"))).await.unwrap().unwrap();
+        assert_eq!(response.into_inner().status, ServingStatus::Serving as i32);
+
+        let mut stream = timeout(WAIT, service.watch(request("database")))
+            .await
+            .unwrap()
+            .unwrap()
+            .into_inner();
+        let first = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(first.status, ServingStatus::Serving as i32);
+
+        let mut publisher = component.publisher();
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+        let next = timeout(WAIT, stream.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(next.status, ServingStatus::NotServing as i32);
+    }
+}
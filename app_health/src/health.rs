@@ -56,6 +56,29 @@ pub const ALL_HEALTH_STATES: [Health; NUM_HEALTH_STATES] = [
     Health::Unrecoverable,
 ];
 
+impl Health {
+    /// The next worse state, or the same state if there is none.
+    pub(crate) const fn worse(self) -> Self {
+        match self {
+            Self::Nominal => Self::Degraded,
+            Self::Degraded => Self::Critical,
+            Self::Critical => Self::Down,
+            Self::Down | Self::Unrecoverable => Self::Unrecoverable,
+        }
+    }
+
+    /// The color used to draw the state in diagrams.
+    pub(crate) const fn color(self) -> &'static str {
+        match self {
+            Self::Nominal => "#4caf50",
+            Self::Degraded => "#ffc107",
+            Self::Critical => "#ff9800",
+            Self::Down => "#f44336",
+            Self::Unrecoverable => "#9c27b0",
+        }
+    }
+}
+
 impl Display for Health {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let s = match self {
@@ -0,0 +1,73 @@
+use crate::{Attribute, Health};
+
+/// An active probe of a component's health.
+///
+/// Rather than pushing signals through a [`Publisher`](crate::Publisher), a component can register checks which
+/// are run periodically in the background. The outcome of each run becomes the signal of a publisher owned by the
+/// check. Checks are registered with [`Component::health_check`](crate::Component::health_check).
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Attribute, Health, HealthCheck};
+/// use std::time::Duration;
+///
+/// struct Ping;
+///
+/// impl HealthCheck for Ping {
+///     async fn check(&self) -> (Health, Vec<Attribute>) {
+///         // a real check would talk to the database here
+///         (Health::Nominal, vec![("endpoint", "db.internal:5432").into()])
+///     }
+/// }
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let database = aggregator.component("database");
+///
+///     database
+///         .health_check(Ping)
+///         .interval(Duration::from_secs(30))
+///         .timeout(Duration::from_secs(2))
+///         .start();
+/// }
+/// ```
+pub trait HealthCheck: Send + Sync {
+    /// Probe the health of the component, returning its state along with attributes that provide context.
+    fn check(&self) -> impl Future<Output = (Health, Vec<Attribute>)> + Send;
+}
+
+/// Map a measurement onto a health state, escalating once it reaches either threshold.
+#[cfg(feature = "checks")]
+pub fn grade<T: PartialOrd + Copy>(value: T, degraded: T, critical: T) -> Health {
+    if value >= critical {
+        Health::Critical
+    } else if value >= degraded {
+        Health::Degraded
+    } else {
+        Health::Nominal
+    }
+}
+
+/// Convert a measurement into an attribute value, saturating if it doesn't fit.
+#[cfg(feature = "checks")]
+pub fn saturating_i64(value: impl TryInto<i64>) -> i64 {
+    value.try_into().unwrap_or(i64::MAX)
+}
+
+#[cfg(all(test, feature = "checks"))]
+mod tests {
+    use super::*;
+    use core::cmp::Reverse;
+
+    #[test]
+    fn grade_escalates() {
+        assert_eq!(grade(1, 5, 10), Health::Nominal);
+        assert_eq!(grade(5, 5, 10), Health::Degraded);
+        assert_eq!(grade(11, 5, 10), Health::Critical);
+
+        // thresholds on values that get worse as they shrink
+        assert_eq!(grade(Reverse(3), Reverse(5), Reverse(1)), Health::Degraded);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::sync::{oneshot, watch};
+use crate::{Attribute, Health, HealthCheck, HealthCheckHandle, Publisher, Report, Runtime};
+use core::future::pending;
+use core::hash::{BuildHasher, Hasher};
+use core::pin::pin;
+use core::time::Duration;
+use futures_util::{FutureExt, select};
+use std::collections::hash_map::RandomState;
+use std::sync::Arc;
+
+/// The default interval between runs of a health check.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The default time a health check is given to complete.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The shortest interval between runs of a health check, so that a zero interval doesn't keep the worker spinning.
+const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Configures and starts a [`HealthCheck`].
+///
+/// Builders are created with [`Component::health_check`](crate::Component::health_check).
+///
+/// Once started, the check runs immediately and then once per interval, until the component it belongs to is dropped or
+/// the check is stopped with [`HealthCheckHandle::stop`].
+#[derive(Debug)]
+pub struct HealthCheckBuilder<C> {
+    check: C,
+    publisher: Publisher,
+    interval: Duration,
+    timeout: Duration,
+    jitter: Duration,
+    timeout_state: Health,
+    runtime: Arc<dyn Runtime>,
+    health_rx: watch::Receiver<Report>,
+}
+
+impl<C: HealthCheck + 'static> HealthCheckBuilder<C> {
+    pub(crate) const fn new(check: C, publisher: Publisher, runtime: Arc<dyn Runtime>, health_rx: watch::Receiver<Report>) -> Self {
+        Self {
+            check,
+            publisher,
+            interval: DEFAULT_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+            jitter: Duration::ZERO,
+            timeout_state: Health::Critical,
+            runtime,
+            health_rx,
+        }
+    }
+
+    /// Set the interval between runs of the check.
+    ///
+    /// Intervals shorter than 100 milliseconds are raised to 100 milliseconds.
+    ///
+    /// Defaults to 10 seconds.
+    #[must_use]
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how long each run of the check may take before it's abandoned.
+    ///
+    /// Defaults to 5 seconds.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the maximum random delay added to each interval.
+    ///
+    /// Jitter spreads out checks that would otherwise run in lockstep, for example across many instances of
+    /// an application that were started at the same time.
+    ///
+    /// Defaults to zero.
+    #[must_use]
+    pub const fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the health state reported when a run of the check times out.
+    ///
+    /// The signal produced on timeout carries a `timed_out=true` attribute.
+    ///
+    /// Defaults to [`Health::Critical`].
+    #[must_use]
+    pub const fn timeout_state(mut self, state: Health) -> Self {
+        self.timeout_state = state;
+        self
+    }
+
+    /// Start running the check in the background.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the aggregator's [`Runtime`] can't spawn the check's worker, such as when using the default
+    /// [`TokioRuntime`](crate::TokioRuntime) from outside of a Tokio runtime.
+    pub fn start(self) -> HealthCheckHandle {
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let runtime = Arc::clone(&self.runtime);
+        runtime.spawn(Box::pin(check_worker(self, stop_rx)));
+        HealthCheckHandle::new(stop_tx)
+    }
+}
+
+async fn check_worker<C: HealthCheck>(mut builder: HealthCheckBuilder<C>, stop_rx: oneshot::Receiver<()>) {
+    // we exit once the component has gone away, since nobody can observe our results anymore, without waiting for the
+    // current run or interval to end so that the publisher doesn't hold up a shutdown
+    let mut health_rx = builder.health_rx.clone();
+    let mut stopped = pin!(
+        async move {
+            select! {
+                // a handle that's dropped rather than stopped leaves the check running
+                result = stop_rx.fuse() => if result.is_err() { pending::<()>().await },
+                () = async { while health_rx.changed().await.is_ok() {} }.fuse() => {}
+            }
+        }
+        .fuse()
+    );
+
+    let interval = builder.interval.max(MIN_INTERVAL);
+
+    while builder.publisher.is_connected() {
+        let timed_out = sleep_for(&*builder.runtime, builder.timeout);
+        let (state, attributes) = select! {
+            () = stopped => return,
+            result = builder.check.check().fuse() => result,
+            () = timed_out.fuse() => (builder.timeout_state, vec![Attribute::from(("timed_out", true))]),
+        };
+
+        builder.publisher.publish(state, attributes);
+
+        let next_run = sleep_for(&*builder.runtime, interval.saturating_add(random_delay(builder.jitter)));
+        select! {
+            () = stopped => return,
+            () = next_run.fuse() => {}
+        }
+    }
+}
+
+/// Returns a future that resolves once the given duration has passed, or never if the deadline can't be represented.
+fn sleep_for(runtime: &dyn Runtime, duration: Duration) -> impl Future<Output = ()> + use<> {
+    let sleep = runtime.now().checked_add(duration).map(|deadline| runtime.sleep_until(deadline));
+
+    async move {
+        match sleep {
+            Some(sleep) => sleep.await,
+            None => pending().await,
+        }
+    }
+}
+
+/// Pick a random delay between zero and `max`, inclusive.
+fn random_delay(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+
+    let nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_nanos(random % nanos.saturating_add(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aggregator, TokioRuntime};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counting(Arc<AtomicUsize>);
+
+    impl HealthCheck for Counting {
+        async fn check(&self) -> (Health, Vec<Attribute>) {
+            let runs = self.0.fetch_add(1, Ordering::Relaxed) + 1;
+            let state = if runs == 1 { Health::Degraded } else { Health::Nominal };
+            (state, vec![Attribute::from(("runs", i64::try_from(runs).unwrap()))])
+        }
+    }
+
+    struct Hanging;
+
+    impl HealthCheck for Hanging {
+        async fn check(&self) -> (Health, Vec<Attribute>) {
+            pending().await
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn runs_periodically() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator.component("database");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let _handle = component
+            .health_check(Counting(Arc::clone(&runs)))
+            .interval(Duration::from_secs(1))
+            .jitter(Duration::from_millis(100))
+            .start();
+
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Degraded);
+
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Nominal);
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reports_timeouts() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator.component("database");
+
+        let _handle = component
+            .health_check(Hanging)
+            .timeout(Duration::from_secs(1))
+            .timeout_state(Health::Down)
+            .start();
+
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Down);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn zero_interval_is_raised() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator.component("database");
+        let runs = Arc::new(AtomicUsize::new(0));
+        let started = tokio::time::Instant::now();
+
+        let _handle = component.health_check(Counting(Arc::clone(&runs))).interval(Duration::ZERO).start();
+
+        component.changed().await.unwrap();
+        component.changed().await.unwrap();
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+        assert!(started.elapsed() >= MIN_INTERVAL);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn huge_durations_never_elapse() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator.component("database");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let _handle = component
+            .health_check(Counting(Arc::clone(&runs)))
+            .interval(Duration::MAX)
+            .timeout(Duration::MAX)
+            .jitter(Duration::MAX)
+            .start();
+
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Degraded);
+
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stopping_withdraws_the_signal() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator.component("database");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let handle = component.health_check(Counting(Arc::clone(&runs))).start();
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Degraded);
+        assert!(!handle.is_finished());
+
+        handle.stop();
+        component.changed().await.unwrap();
+        assert_eq!(component.state(), Health::Nominal);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropping_the_component_ends_a_long_interval() {
+        let aggregator = Aggregator::builder()
+            .component_debounce_interval(Duration::ZERO)
+            .runtime(TokioRuntime::new())
+            .build();
+        let mut component = aggregator.component("database");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let handle = component
+            .health_check(Counting(Arc::clone(&runs)))
+            .interval(Duration::from_secs(3600))
+            .start();
+        component.changed().await.unwrap();
+
+        aggregator.begin_shutdown(Duration::from_secs(60));
+        drop(component);
+
+        let started = tokio::time::Instant::now();
+        assert!(aggregator.shutdown_complete().await);
+        assert!(started.elapsed() < Duration::from_secs(60));
+        assert!(handle.is_finished());
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn random_delay_is_bounded() {
+        assert_eq!(random_delay(Duration::ZERO), Duration::ZERO);
+        for _ in 0..100 {
+            assert!(random_delay(Duration::from_millis(5)) <= Duration::from_millis(5));
+        }
+    }
+}
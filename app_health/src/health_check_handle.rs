@@ -0,0 +1,31 @@
+use crate::sync::oneshot;
+
+/// Controls a [`HealthCheck`](crate::HealthCheck) running in the background.
+///
+/// Handles are returned by [`HealthCheckBuilder::start`](crate::HealthCheckBuilder::start). Dropping a handle leaves
+/// the check running until its component is dropped, while [`stop`](Self::stop) ends it right away.
+#[derive(Debug)]
+pub struct HealthCheckHandle {
+    stop_tx: oneshot::Sender<()>,
+}
+
+impl HealthCheckHandle {
+    pub(crate) const fn new(stop_tx: oneshot::Sender<()>) -> Self {
+        Self { stop_tx }
+    }
+
+    /// Stop running the check.
+    ///
+    /// A run that's in progress is abandoned, and the check's signal is withdrawn from its component.
+    pub fn stop(self) {
+        // the check has already exited if nobody is listening
+        let _ = self.stop_tx.send(());
+    }
+
+    /// Returns `true` once the check has stopped, either because of [`stop`](Self::stop) or because its component
+    /// was dropped.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.stop_tx.is_canceled()
+    }
+}
@@ -0,0 +1,141 @@
+use crate::Health;
+use core::fmt::Display;
+use core::time::Duration;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// An operator's decision to force a health state for a while, regardless of what publishers report.
+///
+/// Overriding the health of the whole application with [`Aggregator::override_health`](crate::Aggregator::override_health)
+/// can fail its readiness probe to drain it from a load balancer, or force it [`Nominal`](Health::Nominal) to stop a
+/// restart loop while a problem is investigated. A single component can be overridden with
+/// [`Component::override_health`](crate::Component::override_health).
+///
+/// Overrides always end on their own once their duration has passed, and they're included in reports along with who set
+/// them and why, see [`Report::health_override`](crate::Report::health_override) and
+/// [`Reports::health_override`](crate::Reports::health_override).
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Health, HealthOverride};
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///
+///     // fail readiness, but not liveness, to drain the application from its load balancer
+///     aggregator.override_health(
+///         HealthOverride::new(Health::Critical, Duration::from_secs(10 * 60))
+///             .by("alice")
+///             .because("draining before a kernel upgrade"),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthOverride {
+    state: Health,
+    duration: Duration,
+    set_by: Option<Arc<str>>,
+    reason: Option<Arc<str>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339"))]
+    pub(crate) started: SystemTime,
+}
+
+impl HealthOverride {
+    /// Describe an override that forces the given state for the given duration.
+    #[must_use]
+    pub fn new(state: Health, duration: Duration) -> Self {
+        Self {
+            state,
+            duration,
+            set_by: None,
+            reason: None,
+            started: SystemTime::now(),
+        }
+    }
+
+    /// Record who is setting the override.
+    #[must_use]
+    pub fn by(mut self, operator: impl AsRef<str>) -> Self {
+        self.set_by = Some(operator.as_ref().into());
+        self
+    }
+
+    /// Record why the override is being set.
+    #[must_use]
+    pub fn because(mut self, reason: impl AsRef<str>) -> Self {
+        self.reason = Some(reason.as_ref().into());
+        self
+    }
+
+    /// The state being forced.
+    #[must_use]
+    pub const fn state(&self) -> Health {
+        self.state
+    }
+
+    /// How long the override lasts once it's set.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Who set the override, if recorded.
+    #[must_use]
+    pub fn set_by(&self) -> Option<&str> {
+        self.set_by.as_deref()
+    }
+
+    /// Why the override was set, if recorded.
+    #[must_use]
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// When the override was set.
+    #[must_use]
+    pub const fn started(&self) -> SystemTime {
+        self.started
+    }
+
+    /// When the override ends on its own.
+    ///
+    /// This returns `None` if the expiry is too far in the future to be represented.
+    #[must_use]
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.started.checked_add(self.duration)
+    }
+}
+
+impl Display for HealthOverride {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "forced {}", self.state)?;
+        if let Some(set_by) = &self.set_by {
+            write!(f, " by {set_by}")?;
+        }
+
+        if let Some(reason) = &self.reason {
+            write!(f, ": {reason}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_who_and_why() {
+        let o = HealthOverride::new(Health::Down, Duration::from_secs(60));
+        assert_eq!(o.to_string(), "forced Down");
+
+        let o = o.by("alice").because("draining");
+        assert_eq!(o.to_string(), "forced Down by alice: draining");
+        assert_eq!(o.expires(), Some(o.started() + Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{Aggregator, Filter, Health, ProbeStatus, Probes, Report};
+use axum::Json;
+use axum::Router;
+use axum::extract::{RawQuery, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use core::net::{Ipv4Addr, SocketAddr};
+use serde::Serialize;
+use std::io;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+const DEFAULT_ADDRESS: SocketAddr = SocketAddr::new(core::net::IpAddr::V4(Ipv4Addr::UNSPECIFIED), 8080);
+
+/// Serves the health of an application over HTTP.
+///
+/// The server exposes three routes:
+///
+/// - `/healthz`: The overall health of the application, as returned by [`Aggregator::state`].
+/// - `/livez`: The outcome of the liveness probe, as returned by [`Aggregator::liveness`].
+/// - `/readyz`: The outcome of the readiness probe, as returned by [`Aggregator::readiness`].
+///
+/// Each route responds with `200 OK` when the application is healthy and with `503 Service Unavailable`
+/// otherwise. For `/healthz`, the application is considered unhealthy once its overall health reaches the
+/// threshold set with [`unhealthy_threshold`](Self::unhealthy_threshold).
+///
+/// The response body is a JSON object with a `status` field. When the `verbose` query parameter
+/// is present, the body also includes a `components` field holding a [`Report`] for each
+/// relevant component. The `filter` query parameter controls which signals are included in those reports, using a
+/// comma-separated list of health states such as `?verbose&filter=degraded,critical`. The filter defaults to `all`.
+/// Adding `history` to the list also includes each component's recent state transitions.
+///
+/// # Example
+///
+/// ```no_run
+/// use app_health::{Aggregator, HttpServer};
+/// use std::sync::Arc;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> std::io::Result<()> {
+///     let aggregator = Arc::new(Aggregator::new());
+///     let _database = aggregator.component("database");
+///
+///     HttpServer::new(aggregator).address(([127, 0, 0, 1], 9090).into()).serve().await
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpServer {
+    aggregator: Arc<Aggregator>,
+    address: SocketAddr,
+    unhealthy_threshold: Health,
+}
+
+impl HttpServer {
+    /// Create a server for the given aggregator.
+    #[must_use]
+    pub const fn new(aggregator: Arc<Aggregator>) -> Self {
+        Self {
+            aggregator,
+            address: DEFAULT_ADDRESS,
+            unhealthy_threshold: Health::Critical,
+        }
+    }
+
+    /// Set the address the server listens on.
+    ///
+    /// Defaults to `0.0.0.0:8080`.
+    #[must_use]
+    pub const fn address(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Set the health state at which `/healthz` starts reporting the application as unavailable.
+    ///
+    /// Defaults to [`Health::Critical`].
+    #[must_use]
+    pub const fn unhealthy_threshold(mut self, threshold: Health) -> Self {
+        self.unhealthy_threshold = threshold;
+        self
+    }
+
+    /// Get a router serving the health routes, which can be merged into an existing application.
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/healthz", get(healthz))
+            .route("/livez", get(livez))
+            .route("/readyz", get(readyz))
+            .with_state(self.clone())
+    }
+
+    /// Listen on the configured address and serve the health routes.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the address can't be bound, or if the server encounters an I/O error.
+    pub async fn serve(self) -> io::Result<()> {
+        let listener = TcpListener::bind(self.address).await?;
+        self.serve_on(listener).await
+    }
+
+    /// Serve the health routes on an existing listener, ignoring the configured address.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the server encounters an I/O error.
+    pub async fn serve_on(self, listener: TcpListener) -> io::Result<()> {
+        axum::serve(listener, self.router()).await
+    }
+
+    async fn respond<T: Serialize>(&self, healthy: bool, status: T, query: Option<String>, probes: Probes) -> Response {
+        let options = match Options::parse(query.as_deref()) {
+            Ok(options) => options,
+            Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+        };
+
+        let components = if options.verbose {
+            let Some(reports) = self.aggregator.reports(options.filter).await else {
+                return StatusCode::SERVICE_UNAVAILABLE.into_response();
+            };
+
+            Some(reports.filter(|r| r.probes().intersects(probes)).collect())
+        } else {
+            None
+        };
+
+        let code = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+        (code, Json(Body { status, components })).into_response()
+    }
+}
+
+#[derive(Serialize)]
+struct Body<T> {
+    status: T,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<Vec<Report>>,
+}
+
+async fn healthz(State(server): State<HttpServer>, RawQuery(query): RawQuery) -> Response {
+    let state = server.aggregator.state();
+    server
+        .respond(state < server.unhealthy_threshold, state, query, Probes::all())
+        .await
+}
+
+async fn livez(State(server): State<HttpServer>, RawQuery(query): RawQuery) -> Response {
+    let status = server.aggregator.liveness();
+    server.respond(status == ProbeStatus::Pass, status, query, Probes::LIVENESS).await
+}
+
+async fn readyz(State(server): State<HttpServer>, RawQuery(query): RawQuery) -> Response {
+    let status = server.aggregator.readiness();
+    server.respond(status == ProbeStatus::Pass, status, query, Probes::READINESS).await
+}
+
+/// Options parsed from a request's query string.
+struct Options {
+    verbose: bool,
+    filter: Filter,
+}
+
+impl Options {
+    fn parse(query: Option<&str>) -> Result<Self, String> {
+        let mut options = Self {
+            verbose: false,
+            filter: Filter::ALL,
+        };
+
+        for (key, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+            match &*key {
+                "verbose" => {
+                    options.verbose = match &*value {
+                        "" | "true" | "1" => true,
+                        "false" | "0" => false,
+                        _ => return Err(format!("invalid value for 'verbose': {value}")),
+                    };
+                }
+
+                "filter" => {
+                    options.filter = Filter::empty();
+                    for name in value.split(',').filter(|n| !n.is_empty()) {
+                        options.filter |= parse_filter(name).ok_or_else(|| format!("invalid value for 'filter': {name}"))?;
+                    }
+                }
+
+                // unknown parameters are ignored so that callers can add their own (e.g. cache busters)
+                _ => {}
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_filter(name: &str) -> Option<Filter> {
+    let filter = match name.to_ascii_lowercase().as_str() {
+        "nominal" => Filter::NOMINAL,
+        "degraded" => Filter::DEGRADED,
+        "critical" => Filter::CRITICAL,
+        "down" => Filter::DOWN,
+        "unrecoverable" => Filter::UNRECOVERABLE,
+        "all" => Filter::ALL,
+        "history" => Filter::HISTORY,
+        _ => return None,
+    };
+
+    Some(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::time::timeout;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    async fn get(address: SocketAddr, path: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).await.unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let code = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (code, body.to_owned())
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let options = Options::parse(None).unwrap();
+        assert!(!options.verbose);
+        assert_eq!(options.filter.bits(), Filter::ALL.bits());
+    }
+
+    #[test]
+    fn parse_verbose_and_filter() {
+        let options = Options::parse(Some("verbose&filter=degraded,Critical")).unwrap();
+        assert!(options.verbose);
+        assert_eq!(options.filter.bits(), (Filter::DEGRADED | Filter::CRITICAL).bits());
+
+        let options = Options::parse(Some("verbose=false&other=1")).unwrap();
+        assert!(!options.verbose);
+    }
+
+    #[test]
+    fn parse_decodes_values() {
+        let options = Options::parse(Some("filter=degraded%2Ccritical&verbose=%74rue")).unwrap();
+        assert!(options.verbose);
+        assert_eq!(options.filter.bits(), (Filter::DEGRADED | Filter::CRITICAL).bits());
+
+        let error = Options::parse(Some("filter=down+")).err();
+        assert_eq!(error.as_deref(), Some("invalid value for 'filter': down "));
+    }
+
+    #[test]
+    fn parse_rejects_bad_values() {
+        assert!(Options::parse(Some("filter=sideways")).is_err());
+        assert!(Options::parse(Some("verbose=maybe")).is_err());
+    }
+
+    #[tokio::test]
+    async fn serves_routes() {
+        let aggregator = Arc::new(
+            Aggregator::builder()
+                .debounce_interval(Duration::ZERO)
+                .component_debounce_interval(Duration::ZERO)
+                .build(),
+        );
+
+        let mut component = aggregator.component("database");
+        let mut publisher = component.publisher();
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(tokio::spawn(HttpServer::new(Arc::clone(&aggregator)).serve_on(listener)));
+
+        // wait for the aggregator to catch up with the component
+        while aggregator.state() != Health::Critical {
+            tokio::task::yield_now().await;
+        }
+
+        let (code, body) = get(address, "/healthz").await;
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(code, 503);
+        assert_eq!(body["status"], "Critical");
+        assert!(body.get("components").is_none());
+
+        let (code, body) = get(address, "/livez?verbose&filter=critical").await;
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(code, 200);
+        assert_eq!(body["status"], "Pass");
+        assert_eq!(body["components"][0]["name"], "database");
+
+        let (code, _) = get(address, "/readyz").await;
+        assert_eq!(code, 503);
+
+        let (code, _) = get(address, "/healthz?filter=bogus").await;
+        assert_eq!(code, 400);
+    }
+}
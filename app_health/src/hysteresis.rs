@@ -0,0 +1,248 @@
+use crate::{Escalation, Health, Runtime};
+use core::future::pending;
+use core::time::Duration;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Stabilizes a component's health state over time.
+///
+/// Worsening states take effect immediately, but improvements only take effect once they've held for the
+/// recovery delay. Changes to the stabilized state are counted, and a component changing state too often
+/// within the flap window is considered to be flapping. A component that stays in the same state for long
+/// enough to trigger one of its escalation rules is reported in the next worse state.
+#[derive(Debug)]
+pub struct Hysteresis {
+    recovery_delay: Duration,
+    flap_detection: Option<FlapDetection>,
+    state: Health,
+    recovering_since: Option<Instant>,
+    changes: VecDeque<Instant>,
+    escalations: Vec<Escalation>,
+    entered_at: Instant,
+    escalation: Option<Escalation>,
+    runtime: Arc<dyn Runtime>,
+}
+
+/// Settings controlling when a component is considered to be flapping.
+#[derive(Debug, Clone, Copy)]
+pub struct FlapDetection {
+    pub max_changes: usize,
+    pub window: Duration,
+    pub state: Option<Health>,
+}
+
+impl Hysteresis {
+    pub fn new(
+        recovery_delay: Duration,
+        flap_detection: Option<FlapDetection>,
+        escalations: Vec<Escalation>,
+        runtime: Arc<dyn Runtime>,
+    ) -> Self {
+        Self {
+            recovery_delay,
+            flap_detection,
+            state: Health::Nominal,
+            recovering_since: None,
+            changes: VecDeque::new(),
+            escalations,
+            entered_at: runtime.now(),
+            escalation: None,
+            runtime,
+        }
+    }
+
+    /// Feed the latest state derived from publisher signals.
+    pub fn update(&mut self, signal_state: Health) {
+        let now = self.runtime.now();
+
+        if signal_state >= self.state {
+            self.recovering_since = None;
+            if signal_state != self.state {
+                self.change(signal_state, now);
+            }
+        } else {
+            let since = *self.recovering_since.get_or_insert(now);
+            if now.duration_since(since) >= self.recovery_delay {
+                self.recovering_since = None;
+                self.change(signal_state, now);
+            }
+        }
+
+        if let Some(flap) = self.flap_detection {
+            while self.changes.front().is_some_and(|t| now.duration_since(*t) >= flap.window) {
+                let _ = self.changes.pop_front();
+            }
+        }
+
+        self.escalation = self.rule().filter(|rule| now.duration_since(self.entered_at) >= rule.after());
+    }
+
+    /// The escalation rule that applies to the current stabilized state, if any.
+    fn rule(&self) -> Option<Escalation> {
+        self.escalations.iter().find(|rule| rule.from() == self.state).copied()
+    }
+
+    fn change(&mut self, state: Health, now: Instant) {
+        self.state = state;
+        self.entered_at = now;
+        if self.flap_detection.is_some() {
+            self.changes.push_back(now);
+        }
+    }
+
+    /// The stabilized state, escalated if the component has been in it for too long or is flapping.
+    pub fn state(&self) -> Health {
+        let state = self.escalation.map_or(self.state, |rule| rule.to());
+        match self.flap_detection {
+            Some(FlapDetection { state: Some(flapping), .. }) if self.is_flapping() => state.max(flapping),
+            _ => state,
+        }
+    }
+
+    /// The escalation rule currently in effect, if any.
+    pub const fn escalation(&self) -> Option<Escalation> {
+        self.escalation
+    }
+
+    /// Whether the stabilized state has changed too often recently.
+    pub fn is_flapping(&self) -> bool {
+        self.flap_detection.is_some_and(|f| self.changes.len() > f.max_changes)
+    }
+
+    /// Returns a future that resolves when the stabilized state or the flapping status may change without further input.
+    pub fn expired(&self) -> impl Future<Output = ()> + use<> {
+        // deadlines too far in the future to be represented never arrive
+        let recovery = self.recovering_since.and_then(|since| since.checked_add(self.recovery_delay));
+        let flap = self
+            .flap_detection
+            .zip(self.changes.front())
+            .and_then(|(f, t)| t.checked_add(f.window));
+
+        let escalation = self
+            .rule()
+            .filter(|_| self.escalation.is_none())
+            .and_then(|rule| self.entered_at.checked_add(rule.after()));
+
+        let sleep = [recovery, flap, escalation]
+            .into_iter()
+            .flatten()
+            .min()
+            .map(|deadline| self.runtime.sleep_until(deadline));
+
+        async move {
+            match sleep {
+                Some(sleep) => sleep.await,
+                None => pending().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TokioRuntime;
+    use tokio::time::advance;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[tokio::test(start_paused = true)]
+    async fn recovery_is_delayed() {
+        let mut hysteresis = Hysteresis::new(SECOND * 10, None, Vec::new(), Arc::new(TokioRuntime::new()));
+
+        hysteresis.update(Health::Critical);
+        assert_eq!(hysteresis.state(), Health::Critical);
+
+        hysteresis.update(Health::Nominal);
+        assert_eq!(hysteresis.state(), Health::Critical);
+
+        advance(SECOND * 5).await;
+        hysteresis.update(Health::Nominal);
+        assert_eq!(hysteresis.state(), Health::Critical);
+
+        // worsening again restarts the clock
+        hysteresis.update(Health::Critical);
+        hysteresis.update(Health::Degraded);
+        advance(SECOND * 9).await;
+        hysteresis.update(Health::Degraded);
+        assert_eq!(hysteresis.state(), Health::Critical);
+
+        hysteresis.expired().await;
+        hysteresis.update(Health::Degraded);
+        assert_eq!(hysteresis.state(), Health::Degraded);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn huge_recovery_delay_never_recovers() {
+        let mut hysteresis = Hysteresis::new(Duration::MAX, None, Vec::new(), Arc::new(TokioRuntime::new()));
+
+        hysteresis.update(Health::Critical);
+        hysteresis.update(Health::Nominal);
+        assert!(tokio::time::timeout(SECOND * 60, hysteresis.expired()).await.is_err());
+
+        hysteresis.update(Health::Nominal);
+        assert_eq!(hysteresis.state(), Health::Critical);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn flapping_is_detected() {
+        let flap = FlapDetection {
+            max_changes: 3,
+            window: SECOND * 60,
+            state: Some(Health::Critical),
+        };
+
+        let mut hysteresis = Hysteresis::new(Duration::ZERO, Some(flap), Vec::new(), Arc::new(TokioRuntime::new()));
+        for _ in 0..2 {
+            hysteresis.update(Health::Degraded);
+            advance(SECOND).await;
+            hysteresis.update(Health::Nominal);
+            advance(SECOND).await;
+        }
+
+        assert!(hysteresis.is_flapping());
+        assert_eq!(hysteresis.state(), Health::Critical);
+
+        // once the changes age out of the window, the component settles down
+        advance(SECOND * 60).await;
+        hysteresis.update(Health::Nominal);
+        assert!(!hysteresis.is_flapping());
+        assert_eq!(hysteresis.state(), Health::Nominal);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lingering_states_escalate() {
+        let rule = Escalation::new(Health::Degraded, SECOND * 300);
+        let mut hysteresis = Hysteresis::new(Duration::ZERO, None, vec![rule], Arc::new(TokioRuntime::new()));
+
+        hysteresis.update(Health::Degraded);
+        advance(SECOND * 299).await;
+        hysteresis.update(Health::Degraded);
+        assert_eq!(hysteresis.state(), Health::Degraded);
+        assert_eq!(hysteresis.escalation(), None);
+
+        hysteresis.expired().await;
+        hysteresis.update(Health::Degraded);
+        assert_eq!(hysteresis.state(), Health::Critical);
+        assert_eq!(hysteresis.escalation(), Some(rule));
+
+        // leaving the state resets the clock
+        hysteresis.update(Health::Nominal);
+        hysteresis.update(Health::Degraded);
+        assert_eq!(hysteresis.state(), Health::Degraded);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn huge_escalation_delay_never_escalates() {
+        let rule = Escalation::new(Health::Degraded, Duration::MAX);
+        let mut hysteresis = Hysteresis::new(Duration::ZERO, None, vec![rule], Arc::new(TokioRuntime::new()));
+
+        hysteresis.update(Health::Degraded);
+        assert!(tokio::time::timeout(SECOND * 60, hysteresis.expired()).await.is_err());
+
+        hysteresis.update(Health::Degraded);
+        assert_eq!(hysteresis.state(), Health::Degraded);
+        assert_eq!(hysteresis.escalation(), None);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::Runtime;
+use core::cell::{Cell, RefCell};
+use core::fmt::Debug;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::task::Wake;
+use std::thread;
+use std::time::Instant;
+
+thread_local! {
+    // the earliest deadline of any sleep polled during the current poll of a worker
+    static NEXT_WAKEUP: Cell<Option<Instant>> = const { Cell::new(None) };
+
+    // the runtimes whose workers are being run on this thread, identified by the address of their task list
+    static RUNNING: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A [`Runtime`] that runs workers on the caller's thread, with no background tasks or threads at all.
+///
+/// Rather than running in the background, workers are driven to completion of all their pending work whenever the
+/// application calls into the crate, such as when publishing a signal, querying the state of a component or aggregator, or
+/// polling a future that waits for it to change. Calls made from several threads at once take turns running the workers.
+/// Debouncing is disabled with this runtime, whatever the configured debounce intervals, so every query reflects
+/// everything published before it on any thread.
+///
+/// This suits libraries that must not spawn tasks and synchronous applications that don't want extra threads. The
+/// tradeoff is that time-based behavior, such as leases going stale or health checks running, only happens during those
+/// calls. Futures like [`Aggregator::reports`](crate::Aggregator::reports) complete immediately with this runtime, so
+/// they can be driven with any minimal executor. Futures that wait for a change, like
+/// [`Aggregator::changed`](crate::Aggregator::changed), are woken once a pending timer of the workers is due, so that
+/// the workers get to run again. While such a future waits on a timer, a helper thread sleeps until the timer is due to
+/// wake it. The workers themselves never run on that thread.
+///
+/// The workers of dropped aggregators and components are cleaned up the next time the runtime runs.
+///
+/// This runtime is available with the `inline` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Health, InlineRuntime};
+///
+/// fn main() {
+///     let aggregator = Aggregator::builder().runtime(InlineRuntime::new()).build();
+///
+///     let database = aggregator.component("database");
+///     let mut publisher = database.publisher();
+///
+///     publisher.publish(Health::Degraded, [("reason", "slow queries")]);
+///     assert_eq!(aggregator.state(), Health::Degraded);
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct InlineRuntime {
+    tasks: Arc<Mutex<Vec<Task>>>,
+    spawned: Arc<Mutex<Vec<Task>>>,
+    timer: Arc<Timer>,
+}
+
+/// A worker along with what's needed to tell when it's worth polling again.
+struct Task {
+    future: Pin<Box<dyn Future<Output = ()> + Send>>,
+    waker: Arc<TaskWaker>,
+    deadline: Option<Instant>,
+}
+
+impl InlineRuntime {
+    /// Create a runtime with no workers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Runtime for InlineRuntime {
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>) {
+        let waker = Arc::new(TaskWaker {
+            woken: AtomicBool::new(true),
+        });
+
+        self.spawned.lock().unwrap_or_else(PoisonError::into_inner).push(Task {
+            future,
+            waker,
+            deadline: None,
+        });
+    }
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(Sleep { deadline })
+    }
+
+    fn run_until_stalled(&self) {
+        // calls made by the workers themselves are no-ops, since the outer call picks up whatever they cause
+        let id = Arc::as_ptr(&self.tasks).addr();
+        if RUNNING.with_borrow(|running| running.contains(&id)) {
+            return;
+        }
+
+        // other threads wait for the one running the workers, so that their queries reflect everything they published
+        let mut tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        let _running = Running::enter(id);
+
+        loop {
+            tasks.append(&mut self.spawned.lock().unwrap_or_else(PoisonError::into_inner));
+
+            let now = Instant::now();
+            let mut progress = false;
+            tasks.retain_mut(|task| {
+                let due = task.deadline.is_some_and(|deadline| deadline <= now);
+                if !task.waker.woken.swap(false, Ordering::AcqRel) && !due {
+                    return true;
+                }
+
+                progress = true;
+                NEXT_WAKEUP.set(None);
+                let waker = Waker::from(Arc::clone(&task.waker));
+                let pending = task.future.as_mut().poll(&mut Context::from_waker(&waker)).is_pending();
+                task.deadline = NEXT_WAKEUP.get();
+                pending
+            });
+
+            if !progress {
+                break;
+            }
+        }
+
+        let next_deadline = tasks.iter().filter_map(|task| task.deadline).min();
+        drop(tasks);
+        self.timer.lock().next_deadline = next_deadline;
+    }
+
+    fn wake_when_due(&self, waker: &Waker) {
+        // the workers themselves are already being run, which takes care of their timers
+        let id = Arc::as_ptr(&self.tasks).addr();
+        if RUNNING.with_borrow(|running| running.contains(&id)) {
+            return;
+        }
+
+        let mut state = self.timer.lock();
+        let Some(deadline) = state.next_deadline else {
+            return;
+        };
+
+        if !state
+            .waiters
+            .iter()
+            .any(|(due, waiter)| *due == deadline && waiter.will_wake(waker))
+        {
+            state.waiters.push((deadline, waker.clone()));
+        }
+
+        if !state.sleeping {
+            state.sleeping = true;
+            let timer = Arc::clone(&self.timer);
+            drop(thread::spawn(move || timer.sleep()));
+        }
+
+        drop(state);
+        self.timer.changed.notify_one();
+    }
+
+    fn supports_debouncing(&self) -> bool {
+        false
+    }
+}
+
+/// Marks a runtime's workers as running on the current thread until dropped, even if a worker panics.
+struct Running(usize);
+
+impl Running {
+    fn enter(id: usize) -> Self {
+        RUNNING.with_borrow_mut(|running| running.push(id));
+        Self(id)
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        RUNNING.with_borrow_mut(|running| running.retain(|&id| id != self.0));
+    }
+}
+
+impl Debug for InlineRuntime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InlineRuntime").finish_non_exhaustive()
+    }
+}
+
+/// Marks a worker as ready to be polled again.
+struct TaskWaker {
+    woken: AtomicBool,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+    }
+}
+
+/// Wakes the futures waiting on the workers once the earliest timer of the workers is due.
+#[derive(Default)]
+struct Timer {
+    state: Mutex<TimerState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct TimerState {
+    // the earliest deadline of any worker, as of the last time the workers were run
+    next_deadline: Option<Instant>,
+    waiters: Vec<(Instant, Waker)>,
+    sleeping: bool,
+}
+
+impl Timer {
+    fn lock(&self) -> MutexGuard<'_, TimerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wake each waiter once its deadline has passed, returning once there are no waiters left.
+    fn sleep(&self) {
+        let mut state = self.lock();
+        loop {
+            let now = Instant::now();
+            let (due, waiting) = state.waiters.drain(..).partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+            state.waiters = waiting;
+
+            if !due.is_empty() {
+                // the wakers may call into the application's executor, so they're woken without holding the lock
+                drop(state);
+                for (_, waker) in due {
+                    waker.wake();
+                }
+
+                state = self.lock();
+                continue;
+            }
+
+            let Some(next) = state.waiters.iter().map(|(deadline, _)| *deadline).min() else {
+                state.sleeping = false;
+                return;
+            };
+
+            state = self
+                .changed
+                .wait_timeout(state, next.saturating_duration_since(now))
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+}
+
+/// A future that completes once a deadline has passed, when polled by [`InlineRuntime::run_until_stalled`].
+struct Sleep {
+    deadline: Instant,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        let deadline = NEXT_WAKEUP.get().map_or(self.deadline, |next| next.min(self.deadline));
+        NEXT_WAKEUP.set(Some(deadline));
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aggregator, Filter, Health};
+    use core::time::Duration;
+
+    fn aggregator() -> Aggregator {
+        Aggregator::builder()
+            .runtime(InlineRuntime::new())
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build()
+    }
+
+    /// Run a future to completion, parking the thread while it's pending.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct Unpark(thread::Thread);
+
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut future = core::pin::pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                return output;
+            }
+
+            thread::park();
+        }
+    }
+
+    /// Poll a future that's expected to complete right away.
+    fn now_or_never<F: Future>(future: F) -> F::Output {
+        let mut future = core::pin::pin!(future);
+        match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future didn't complete"),
+        }
+    }
+
+    #[test]
+    fn queries_are_consistent() {
+        let aggregator = aggregator();
+        let database = aggregator.component("database");
+        let mut publisher = database.publisher();
+
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+        assert_eq!(database.state(), Health::Critical);
+        assert_eq!(aggregator.state(), Health::Critical);
+
+        let reports: Vec<_> = now_or_never(aggregator.reports(Filter::ALL)).unwrap().collect();
+        assert_eq!(reports[0].signal_count(Health::Critical), 1);
+
+        drop(publisher);
+        assert_eq!(aggregator.state(), Health::Nominal);
+    }
+
+    #[test]
+    fn queries_see_publishes_from_other_threads() {
+        let aggregator = aggregator();
+        let components: Vec<_> = (0..8).map(|i| aggregator.component(format!("worker-{i}"))).collect();
+
+        // each thread's queries must reflect its own publishes, even while other threads are running the workers
+        thread::scope(|scope| {
+            for component in &components {
+                let _ = scope.spawn(|| {
+                    let mut publisher = component.publisher();
+                    for _ in 0..100 {
+                        publisher.publish(Health::Degraded, [("reason", "busy")]);
+                        assert_eq!(component.state(), Health::Degraded);
+                        publisher.publish(Health::Nominal, [("reason", "idle")]);
+                        assert_eq!(component.state(), Health::Nominal);
+                    }
+                });
+            }
+        });
+
+        let mut publisher = components[0].publisher();
+        thread::scope(|scope| scope.spawn(|| publisher.publish(Health::Critical, [("reason", "stuck")])).join()).unwrap();
+        assert_eq!(aggregator.state(), Health::Critical);
+    }
+
+    #[test]
+    fn timers_fire_when_polled() {
+        let aggregator = aggregator();
+        let database = aggregator.component("database");
+        let publisher = database.publisher_with_ttl(Duration::from_millis(10));
+
+        assert_eq!(aggregator.state(), Health::Nominal);
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(aggregator.state(), Health::Critical);
+
+        publisher.heartbeat();
+        assert_eq!(aggregator.state(), Health::Nominal);
+    }
+
+    #[test]
+    fn debouncing_is_disabled() {
+        let aggregator = Aggregator::builder().runtime(InlineRuntime::new()).build();
+        let database = aggregator.component("database");
+        let mut publisher = database.publisher();
+
+        publisher.publish(Health::Degraded, [("reason", "slow queries")]);
+        assert_eq!(database.state(), Health::Degraded);
+        assert_eq!(aggregator.state(), Health::Degraded);
+    }
+
+    #[test]
+    fn waiting_wakes_once_timers_are_due() {
+        let mut aggregator = Aggregator::builder().runtime(InlineRuntime::new()).build();
+        let database = aggregator.component("database");
+        let _publisher = database.publisher_with_ttl(Duration::from_millis(50));
+        assert_eq!(aggregator.state(), Health::Nominal);
+
+        // nothing calls into the crate while waiting, so only the lease's timer can bring about the change
+        while aggregator.state() != Health::Critical {
+            block_on(aggregator.changed()).unwrap();
+        }
+    }
+}
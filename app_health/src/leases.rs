@@ -0,0 +1,210 @@
+use crate::Runtime;
+use crate::component_state::ComponentState;
+use crate::signal::Signal;
+use core::future::pending;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+/// Tracks the publishers of a component that must renew their signal within a time-to-live.
+///
+/// A publisher that fails to renew its lease in time has its signal replaced by the stale signal
+/// until it publishes or sends a heartbeat again.
+#[expect(clippy::struct_field_names, reason = "field names are clear and unambiguous")]
+pub struct Leases {
+    stale_signal: Signal,
+    leases: HashMap<u64, Lease>,
+    runtime: Arc<dyn Runtime>,
+}
+
+struct Lease {
+    ttl: Duration,
+
+    // `None` when the time-to-live is too long for the lease to ever run out
+    deadline: Option<Instant>,
+    signal: Signal,
+    stale: bool,
+}
+
+impl Lease {
+    /// The signal currently counted in the component's state on behalf of the publisher.
+    const fn counted<'a>(&'a self, stale_signal: &'a Signal) -> &'a Signal {
+        if self.stale { stale_signal } else { &self.signal }
+    }
+}
+
+impl Leases {
+    pub fn new(stale_signal: Signal, runtime: Arc<dyn Runtime>) -> Self {
+        Self {
+            stale_signal,
+            leases: HashMap::new(),
+            runtime,
+        }
+    }
+
+    /// Start tracking a publisher.
+    pub fn start(&mut self, id: u64, ttl: Duration, signal: Signal, state: &mut ComponentState) {
+        state.add_publisher_signal(signal.clone());
+        let _ = self.leases.insert(
+            id,
+            Lease {
+                ttl,
+                deadline: self.runtime.now().checked_add(ttl),
+                signal,
+                stale: false,
+            },
+        );
+    }
+
+    /// Renew a publisher's lease, optionally changing its signal.
+    ///
+    /// Returns `true` if the component's state was modified.
+    pub fn renew(&mut self, id: u64, new_signal: Option<Signal>, state: &mut ComponentState) -> bool {
+        let Some(lease) = self.leases.get_mut(&id) else {
+            return false;
+        };
+
+        let counted = lease.counted(&self.stale_signal).clone();
+        match new_signal {
+            Some(signal) => lease.signal = signal,
+            None => lease.signal.reaffirm(SystemTime::now()),
+        }
+
+        lease.stale = false;
+        lease.deadline = self.runtime.now().checked_add(lease.ttl);
+
+        if counted == lease.signal {
+            state.reaffirm_signal(&lease.signal);
+            return false;
+        }
+
+        state.remove_publisher_signal(counted);
+        state.add_publisher_signal(lease.signal.clone());
+        true
+    }
+
+    /// Stop tracking a publisher.
+    pub fn stop(&mut self, id: u64, state: &mut ComponentState) {
+        if let Some(lease) = self.leases.remove(&id) {
+            state.remove_publisher_signal(lease.counted(&self.stale_signal).clone());
+        }
+    }
+
+    /// Returns a future that resolves when the earliest live lease runs out.
+    pub fn expired(&self) -> impl Future<Output = ()> + use<> {
+        let sleep = self
+            .leases
+            .values()
+            .filter(|l| !l.stale)
+            .filter_map(|l| l.deadline)
+            .min()
+            .map(|deadline| self.runtime.sleep_until(deadline));
+
+        async move {
+            match sleep {
+                Some(sleep) => sleep.await,
+                None => pending().await,
+            }
+        }
+    }
+
+    /// Replace the signals of publishers whose lease has run out with the stale signal.
+    ///
+    /// Returns `true` if the component's state was modified.
+    pub fn expire(&mut self, state: &mut ComponentState) -> bool {
+        let now = self.runtime.now();
+        let mut modified = false;
+
+        for lease in self
+            .leases
+            .values_mut()
+            .filter(|l| !l.stale && l.deadline.is_some_and(|deadline| deadline <= now))
+        {
+            lease.stale = true;
+            if lease.signal != self.stale_signal {
+                state.remove_publisher_signal(lease.signal.clone());
+                state.add_publisher_signal(self.stale_signal.republished());
+                modified = true;
+            }
+        }
+
+        modified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hysteresis::Hysteresis;
+    use crate::{Criticality, Health, Probes, TokioRuntime, WorstOf};
+
+    const TTL: Duration = Duration::from_secs(10);
+
+    fn setup() -> (Leases, ComponentState) {
+        let runtime: Arc<dyn Runtime> = Arc::new(TokioRuntime::new());
+        let leases = Leases::new(Signal::new(Health::Critical, [("stale", true)]), Arc::clone(&runtime));
+        let state = ComponentState::new(
+            "test".into(),
+            Criticality::Required,
+            Probes::all(),
+            0,
+            Hysteresis::new(Duration::ZERO, None, Vec::new(), Arc::clone(&runtime)),
+            Arc::new(WorstOf),
+            runtime,
+        );
+        (leases, state)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn silent_publisher_goes_stale() {
+        let (mut leases, mut state) = setup();
+        leases.start(1, TTL, Signal::nominal(), &mut state);
+        assert_eq!(state.state(), Health::Nominal);
+
+        leases.expired().await;
+        assert!(leases.expire(&mut state));
+        assert_eq!(state.state(), Health::Critical);
+
+        let report = state.make_report(crate::Filter::CRITICAL);
+        let (signal, _) = report.signals(Health::Critical).next().unwrap();
+        assert_eq!(signal.attributes()[0].name().as_str(), "stale");
+
+        // a heartbeat restores the last signal
+        assert!(leases.renew(1, None, &mut state));
+        assert_eq!(state.state(), Health::Nominal);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn renewal_postpones_expiry() {
+        let (mut leases, mut state) = setup();
+        leases.start(1, TTL, Signal::nominal(), &mut state);
+
+        tokio::time::advance(TTL / 2).await;
+        assert!(!leases.renew(1, None, &mut state));
+
+        tokio::time::advance(TTL / 2).await;
+        assert!(!leases.expire(&mut state));
+        assert_eq!(state.state(), Health::Nominal);
+
+        assert!(leases.renew(1, Some(Signal::new(Health::Degraded, [("reason", "slow")])), &mut state));
+        assert_eq!(state.state(), Health::Degraded);
+
+        leases.stop(1, &mut state);
+        assert_eq!(state.state(), Health::Nominal);
+
+        // with no live leases, nothing ever expires
+        assert!(tokio::time::timeout(TTL * 2, leases.expired()).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn huge_ttl_never_expires() {
+        let (mut leases, mut state) = setup();
+        leases.start(1, Duration::MAX, Signal::nominal(), &mut state);
+        assert!(!leases.renew(1, None, &mut state));
+
+        assert!(tokio::time::timeout(TTL, leases.expired()).await.is_err());
+        assert!(!leases.expire(&mut state));
+        assert_eq!(state.state(), Health::Nominal);
+    }
+}
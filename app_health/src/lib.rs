@@ -12,7 +12,9 @@
 //!
 //! 1. **Telemetry**. The application's health state can be reported to a telemetry system for monitoring and alerting.
 //! 2. **Liveness/Readiness Probes**. The application's health state can be used to determine if the application is healthy enough to
-//!    serve traffic (readiness) or if it should be restarted (liveness).
+//!    serve traffic (readiness) or if it should be restarted (liveness). See [`Aggregator::liveness`] and [`Aggregator::readiness`].
+//!    A startup grace period keeps slow-starting components from failing liveness, see [`AggregatorBuilder::startup_grace_period`].
+//!    On the way out, [`Aggregator::begin_shutdown`] fails readiness while the application drains.
 //!
 //! # General Model
 //!
@@ -21,10 +23,19 @@
 //! - **[`Aggregator`]**: The central entity that collects health information from a set of components. It determines the overall health status of the application.
 //!
 //! - **[`Component`]**: Represents a specific feature or subsystem within the application. Each component can have multiple publishers determining its health.
+//!   Components can also have child components, whose health rolls up into their parent's health.
+//!   A component can also depend on other components, in which case it's considered impacted when they go down.
+//!   During planned work, a component can be put into [`Maintenance`] to limit its impact on the application's health.
+//!   Operators can also force the health of a component, or of the whole application, with a [`HealthOverride`].
 //!
 //! - **[`Publisher`]**: An entity that can publish health information regarding a specific component in the application. A single component
 //!   can have multiple publishers. For example, one per thread.
 //!
+//! - **[`HealthCheck`]**: An active probe that a component runs periodically, whose outcome is published on the component's behalf.
+//!
+//! - **[`AggregationPolicy`]**: The rule an aggregator uses to combine the health of its components into the overall health of
+//!   the application. By default, the overall health is that of the least healthy component.
+//!
 #![cfg_attr(feature = "mermaid", doc = simple_mermaid::mermaid!("overall.mmd"))]
 //!
 //! # Reports
@@ -37,18 +48,38 @@
 //!
 //! Both functions accept a **[`Filter`]** parameter which lets you control the level of detail returned in the reports.
 //!
+//! Reports can be rendered in the Prometheus text exposition format with a **[`PrometheusExporter`]**.
+//!
+//! # Features
+//!
+//! - **`tokio`** (default): Adds `TokioRuntime`, which runs the background workers as Tokio tasks. Without this feature, the
+//!   crate doesn't depend on Tokio at all.
+//! - **`threads`**: Adds `ThreadRuntime`, which runs the background workers on dedicated threads for applications without
+//!   an async runtime. Other executors are supported by implementing the `Runtime` trait.
+//! - **`inline`**: Adds `InlineRuntime`, which runs the background workers on the caller's thread whenever the application
+//!   calls into the crate, for libraries that must not spawn tasks or threads. This is the default runtime when it's the only
+//!   runtime feature enabled.
+//! - **`checks`**: Adds ready-made health checks: `TcpConnectCheck`, `DnsResolveCheck`, `DiskSpaceCheck`, `FileFreshnessCheck`,
+//!   and `MemoryCheck`.
+//! - **`serde`**: Implements `Serialize` and `Deserialize` for reports and the types they contain.
+//! - **`grpc`**: Adds `GrpcHealthService`, which implements the standard `grpc.health.v1.Health` service backed by an aggregator.
+//! - **`http`**: Adds `HttpServer`, which serves `/healthz`, `/livez`, and `/readyz` routes backed by an aggregator.
+//! - **`otel`**: Adds `OtelInstrumentation`, which reports health through OpenTelemetry gauges and log events.
+//! - **`mermaid`**: Renders diagrams in the documentation.
+//!
+//! At least one of the `tokio`, `threads`, and `inline` features must be enabled, as they provide the default runtime.
+//!
 //! # Example
 //!
 //! ```rust
-//! use app_health::{Aggregator, Filter};
+//! use app_health::{Aggregator, Filter, Health};
 //! use std::time::Duration;
 //! use tokio::time::sleep;
 //!
 //! #[tokio::main(flavor = "current_thread")]
 //! async fn main() {
 //!     // Create a health aggregator
-//!     use app_health::Health;
-//! let aggregator = Aggregator::new();
+//!     let aggregator = Aggregator::new();
 //!
 //!     // Create components
 //!     let db_component = aggregator.component("database");
@@ -75,31 +106,132 @@
 //! }
 //! ```
 
+#[cfg(not(any(feature = "tokio", feature = "threads", feature = "inline")))]
+compile_error!("at least one of the `tokio`, `threads`, or `inline` features must be enabled to provide a default runtime");
+
+mod aggregation_policy;
 mod aggregator;
+mod aggregator_builder;
 mod attribute;
 mod attribute_string;
 mod attribute_value;
+#[cfg(feature = "checks")]
+mod blocking;
+mod closed;
 mod component;
+mod component_builder;
 mod component_monitor;
 mod component_state;
+mod component_transition;
+mod criticality;
 mod debouncer;
+mod dependencies;
+mod dependency_cycle;
+mod dependency_graph;
+#[cfg(all(feature = "checks", unix))]
+mod disk_space_check;
+#[cfg(feature = "checks")]
+mod dns_resolve_check;
+mod drain_guard;
+mod escalation;
+#[cfg(feature = "checks")]
+mod file_freshness_check;
 mod filter;
+mod flowchart;
+#[cfg(feature = "grpc")]
+mod grpc_service;
 mod health;
+mod health_check;
+mod health_check_builder;
+mod health_check_handle;
+mod health_override;
+#[cfg(feature = "http")]
+mod http_server;
+mod hysteresis;
+#[cfg(feature = "inline")]
+mod inline_runtime;
+mod leases;
+mod maintenance;
+#[cfg(all(feature = "checks", target_os = "linux"))]
+mod memory_check;
+#[cfg(feature = "otel")]
+mod otel;
+mod probe_status;
+mod probes;
+mod prometheus_exporter;
 mod publisher;
+mod quorum;
 mod report;
 mod reports;
+#[cfg(feature = "serde")]
+mod rfc3339;
+mod runtime;
 mod signal;
 mod signals;
+mod sync;
+#[cfg(feature = "checks")]
+mod tcp_connect_check;
+#[cfg(feature = "threads")]
+mod thread_runtime;
+// the tests always run on Tokio
+#[cfg(any(feature = "tokio", test))]
+mod tokio_runtime;
+mod transition;
+mod weighted;
+mod worst_of;
 
+pub use aggregation_policy::AggregationPolicy;
 pub use aggregator::Aggregator;
+pub use aggregator_builder::AggregatorBuilder;
 pub use attribute::Attribute;
 pub use attribute_string::AttributeString;
 pub use attribute_value::AttributeValue;
+pub use closed::Closed;
 pub use component::Component;
+pub use component_builder::ComponentBuilder;
+pub use criticality::Criticality;
+pub use dependency_cycle::DependencyCycle;
+pub use dependency_graph::DependencyGraph;
+#[cfg(all(feature = "checks", unix))]
+pub use disk_space_check::DiskSpaceCheck;
+#[cfg(feature = "checks")]
+pub use dns_resolve_check::DnsResolveCheck;
+pub use escalation::Escalation;
+#[cfg(feature = "checks")]
+pub use file_freshness_check::FileFreshnessCheck;
 pub use filter::Filter;
+#[cfg(feature = "grpc")]
+pub use grpc_service::GrpcHealthService;
 pub use health::Health;
+pub use health_check::HealthCheck;
+pub use health_check_builder::HealthCheckBuilder;
+pub use health_check_handle::HealthCheckHandle;
+pub use health_override::HealthOverride;
+#[cfg(feature = "http")]
+pub use http_server::HttpServer;
+#[cfg(feature = "inline")]
+pub use inline_runtime::InlineRuntime;
+pub use maintenance::Maintenance;
+#[cfg(all(feature = "checks", target_os = "linux"))]
+pub use memory_check::MemoryCheck;
+#[cfg(feature = "otel")]
+pub use otel::OtelInstrumentation;
+pub use probe_status::ProbeStatus;
+pub use probes::Probes;
+pub use prometheus_exporter::PrometheusExporter;
 pub use publisher::Publisher;
+pub use quorum::Quorum;
 pub use report::Report;
 pub use reports::Reports;
+pub use runtime::Runtime;
 pub use signal::Signal;
 pub use signals::Signals;
+#[cfg(feature = "checks")]
+pub use tcp_connect_check::TcpConnectCheck;
+#[cfg(feature = "threads")]
+pub use thread_runtime::ThreadRuntime;
+#[cfg(any(feature = "tokio", test))]
+pub use tokio_runtime::TokioRuntime;
+pub use transition::Transition;
+pub use weighted::Weighted;
+pub use worst_of::WorstOf;
@@ -0,0 +1,125 @@
+use crate::{Attribute, Health};
+use core::time::Duration;
+use std::time::SystemTime;
+
+/// A period during which a component is expected to be unhealthy, such as during a planned database failover.
+///
+/// While a component is in maintenance, its [`effective_state`](crate::Report::effective_state) is capped at the
+/// maintenance [`ceiling`](Self::ceiling), which by default is [`Nominal`](Health::Nominal) so that the component
+/// doesn't affect the application's overall health at all. The component's own state is still reported as-is, and its
+/// [`Report`](crate::Report) carries the maintenance so dashboards can tell that the component is being worked on.
+///
+/// Maintenance always ends on its own, after one hour unless a different duration is set with
+/// [`expires_after`](Self::expires_after). This way a forgotten maintenance can't hide a real outage indefinitely.
+///
+/// Maintenance is started with [`Component::begin_maintenance`](crate::Component::begin_maintenance), or by name with
+/// [`Aggregator::begin_maintenance`](crate::Aggregator::begin_maintenance), which is convenient for admin APIs.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Health, Maintenance};
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let database = aggregator.component("database");
+///
+///     database.begin_maintenance(
+///         Maintenance::new()
+///             .reason(("reason", "planned failover"))
+///             .expires_after(Duration::from_secs(15 * 60))
+///             .cap(Health::Degraded),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Maintenance {
+    reason: Option<Attribute>,
+    duration: Duration,
+    ceiling: Health,
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339"))]
+    pub(crate) started: SystemTime,
+}
+
+impl Maintenance {
+    /// How long maintenance lasts unless [`expires_after`](Self::expires_after) is used.
+    pub const DEFAULT_DURATION: Duration = Duration::from_secs(60 * 60);
+
+    /// Describe a maintenance that excludes the component from overall health for [one hour](Self::DEFAULT_DURATION).
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            reason: None,
+            duration: Self::DEFAULT_DURATION,
+            ceiling: Health::Nominal,
+            started: SystemTime::now(),
+        }
+    }
+
+    /// Attach an attribute explaining the maintenance, such as `("reason", "planned failover")`.
+    #[must_use]
+    pub fn reason(mut self, reason: impl Into<Attribute>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Set how long the maintenance lasts once it begins.
+    #[must_use]
+    pub const fn expires_after(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Cap the health the component contributes to overall health during maintenance, rather than excluding it.
+    ///
+    /// For example, `cap(Health::Degraded)` lets a failing database degrade the application during a failover, without
+    /// taking it down.
+    #[must_use]
+    pub const fn cap(mut self, ceiling: Health) -> Self {
+        self.ceiling = ceiling;
+        self
+    }
+
+    /// The attribute explaining the maintenance, if any.
+    #[must_use]
+    pub const fn reason_attribute(&self) -> Option<&Attribute> {
+        self.reason.as_ref()
+    }
+
+    /// How long the maintenance lasts once it begins.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The most severe health state the component can contribute to overall health during maintenance.
+    #[must_use]
+    pub const fn ceiling(&self) -> Health {
+        self.ceiling
+    }
+
+    /// When the maintenance began.
+    #[must_use]
+    pub const fn started(&self) -> SystemTime {
+        self.started
+    }
+
+    /// When the maintenance ends on its own.
+    ///
+    /// This returns `None` if the expiry is too far in the future to be represented.
+    #[must_use]
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.started.checked_add(self.duration)
+    }
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Eq for Maintenance {}
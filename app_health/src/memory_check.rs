@@ -0,0 +1,93 @@
+use crate::blocking::Blocking;
+use crate::health_check::{grade, saturating_i64};
+use crate::{Attribute, Health, HealthCheck};
+use std::fs;
+use std::io;
+
+/// Checks the resident memory of the current process.
+///
+/// The resident set size is read from `/proc/self/status`. The check reports `Critical` if it can't be read.
+/// Otherwise, it's compared against the thresholds. The check produces the following attributes:
+///
+/// - `rss_bytes`: The resident set size of the process, when it can be read.
+/// - `error`: The reason the resident set size couldn't be read, when it fails.
+///
+/// This check is only available on Linux.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, MemoryCheck};
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let process = aggregator.component("process");
+///
+///     process.health_check(MemoryCheck::new(2 << 30, 4 << 30)).start();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryCheck {
+    degraded_above: u64,
+    critical_above: u64,
+    blocking: Blocking,
+}
+
+impl MemoryCheck {
+    /// Create a check reporting `Degraded` once the resident set size reaches `degraded_above` bytes,
+    /// and `Critical` once it reaches `critical_above` bytes.
+    #[must_use]
+    pub fn new(degraded_above: u64, critical_above: u64) -> Self {
+        Self {
+            degraded_above,
+            critical_above,
+            blocking: Blocking::default(),
+        }
+    }
+}
+
+impl HealthCheck for MemoryCheck {
+    async fn check(&self) -> (Health, Vec<Attribute>) {
+        match self.blocking.run(rss).await {
+            Ok(rss) => (
+                grade(rss, self.degraded_above, self.critical_above),
+                vec![("rss_bytes", saturating_i64(rss)).into()],
+            ),
+
+            Err(e) => (Health::Critical, vec![("error", e.to_string()).into()]),
+        }
+    }
+}
+
+/// Read the resident set size of the current process, in bytes.
+fn rss() -> io::Result<u64> {
+    parse_rss(&fs::read_to_string("/proc/self/status")?).ok_or_else(|| io::Error::other("VmRSS not found in /proc/self/status"))
+}
+
+fn parse_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find_map(|l| l.strip_prefix("VmRSS:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib.saturating_mul(1024))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_status() {
+        assert_eq!(parse_rss("Name:\tapp\nVmRSS:\t    1234 kB\nThreads:\t4\n"), Some(1234 * 1024));
+        assert_eq!(parse_rss("Name:\tapp\n"), None);
+    }
+
+    #[tokio::test]
+    async fn reads_procfs() {
+        let (state, attributes) = MemoryCheck::new(u64::MAX, u64::MAX).check().await;
+        assert_eq!(state, Health::Nominal);
+        assert_eq!(attributes[0].name().as_str(), "rss_bytes");
+
+        let (state, _) = MemoryCheck::new(0, u64::MAX).check().await;
+        assert_eq!(state, Health::Degraded);
+    }
+}
@@ -0,0 +1,411 @@
+use crate::aggregator::{AggregatorMessage, request_reports};
+use crate::component_transition::ComponentTransition;
+use crate::sync::broadcast::RecvError;
+use crate::sync::{broadcast, mpsc, oneshot, watch};
+use crate::{Aggregator, Attribute, AttributeString, AttributeValue, Filter, Health, Runtime};
+use futures_util::{FutureExt, select};
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, Severity};
+use opentelemetry::metrics::{Meter, ObservableGauge};
+use opentelemetry::{Key, KeyValue, StringValue, Value};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+/// The event name given to log records emitted for component transitions.
+const TRANSITION_EVENT_NAME: &str = "app_health.transition";
+
+type ComponentStates = Arc<Mutex<Vec<(Arc<str>, Health)>>>;
+
+/// Reports the health of an application through OpenTelemetry.
+///
+/// Creating an instance registers two observable gauges on the given meter:
+///
+/// - `app_health.state`: The overall health of the application, from `0` (`Nominal`) to `4` (`Unrecoverable`).
+/// - `app_health.component.state`: The health of each component, with the component's name in the `app_health.component` attribute.
+///
+/// In addition, a log record named `app_health.transition` is emitted through the given logger whenever a component
+/// transitions from one health state to another. The record carries the component's name and its old and new states, along with
+/// the attributes of the most common signal in the new state. Signal attributes map losslessly onto OpenTelemetry attributes.
+///
+/// Reporting runs on the aggregator's [`Runtime`](crate::Runtime) and stops when the instance is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, OtelInstrumentation};
+/// use opentelemetry::logs::LoggerProvider;
+/// use opentelemetry::metrics::MeterProvider;
+/// use opentelemetry_sdk::logs::SdkLoggerProvider;
+/// use opentelemetry_sdk::metrics::SdkMeterProvider;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let _database = aggregator.component("database");
+///
+///     let meter = SdkMeterProvider::default().meter("my_app");
+///     let logger = SdkLoggerProvider::builder().build().logger("my_app");
+///     let _instrumentation = OtelInstrumentation::new(&aggregator, &meter, logger);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OtelInstrumentation {
+    _overall_gauge: ObservableGauge<i64>,
+    _component_gauge: ObservableGauge<i64>,
+    _stop_tx: oneshot::Sender<()>,
+}
+
+impl OtelInstrumentation {
+    /// Start reporting the health of the application tracked by the given aggregator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the aggregator's [`Runtime`](crate::Runtime) can't spawn the reporting worker, such as when using the
+    /// default [`TokioRuntime`](crate::TokioRuntime) from outside of a Tokio runtime.
+    #[must_use]
+    pub fn new<L>(aggregator: &Aggregator, meter: &Meter, logger: L) -> Self
+    where
+        L: Logger + Send + 'static,
+    {
+        let health_rx = aggregator.subscribe();
+        let components = ComponentStates::default();
+
+        // runtimes without background workers catch up before each observation
+        let overall_rx = health_rx.clone();
+        let runtime = Arc::clone(aggregator.runtime());
+        let overall_gauge = meter
+            .i64_observable_gauge("app_health.state")
+            .with_description("Overall health of the application (0=Nominal, 4=Unrecoverable).")
+            .with_callback(move |observer| {
+                runtime.run_until_stalled();
+                observer.observe(*overall_rx.borrow() as i64, &[]);
+            })
+            .build();
+
+        let observed = Arc::clone(&components);
+        let runtime = Arc::clone(aggregator.runtime());
+        let component_gauge = meter
+            .i64_observable_gauge("app_health.component.state")
+            .with_description("Health of a component (0=Nominal, 4=Unrecoverable).")
+            .with_callback(move |observer| {
+                runtime.run_until_stalled();
+                for (name, state) in observed.lock().unwrap_or_else(PoisonError::into_inner).iter() {
+                    observer.observe(*state as i64, &[KeyValue::new("app_health.component", Arc::clone(name))]);
+                }
+            })
+            .build();
+
+        let (stop_tx, stop_rx) = oneshot::channel();
+        aggregator.runtime().spawn(Box::pin(otel_worker(
+            health_rx,
+            aggregator.transitions(),
+            aggregator.downgrade(),
+            Arc::clone(aggregator.runtime()),
+            components,
+            logger,
+            stop_rx,
+        )));
+
+        Self {
+            _overall_gauge: overall_gauge,
+            _component_gauge: component_gauge,
+            _stop_tx: stop_tx,
+        }
+    }
+}
+
+async fn otel_worker<L: Logger>(
+    mut health_rx: watch::Receiver<Health>,
+    mut transitions_rx: broadcast::Receiver<ComponentTransition>,
+    aggregator_tx: mpsc::WeakUnboundedSender<AggregatorMessage>,
+    runtime: Arc<dyn Runtime>,
+    components: ComponentStates,
+    logger: L,
+    mut stop_rx: oneshot::Receiver<()>,
+) {
+    refresh(&aggregator_tx, &*runtime, &components).await;
+
+    loop {
+        select! {
+            // the instrumentation has been dropped, so we exit
+            _ = stop_rx => return,
+
+            changed = health_rx.changed().fuse() => {
+                if changed.is_err() {
+                    // the aggregator has been dropped, so we exit
+                    return;
+                }
+
+                refresh(&aggregator_tx, &*runtime, &components).await;
+            }
+
+            transition = transitions_rx.recv().fuse() => {
+                match transition {
+                    Ok(transition) => {
+                        emit(&logger, &transition);
+
+                        // the component's state may have changed without moving the overall health
+                        refresh(&aggregator_tx, &*runtime, &components).await;
+                    }
+
+                    // we fell behind, so some transitions won't be logged, but the gauge still catches up
+                    Err(RecvError::Lagged(_)) => refresh(&aggregator_tx, &*runtime, &components).await,
+
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        }
+    }
+}
+
+/// Refresh the snapshot of component states observed by the per-component gauge.
+async fn refresh(aggregator_tx: &mpsc::WeakUnboundedSender<AggregatorMessage>, runtime: &dyn Runtime, components: &ComponentStates) {
+    if let Some(reports) = request_reports(aggregator_tx, runtime, Filter::empty()).await {
+        let snapshot = reports.map(|r| (Arc::from(r.name()), r.state())).collect();
+        *components.lock().unwrap_or_else(PoisonError::into_inner) = snapshot;
+    }
+}
+
+fn emit<L: Logger>(logger: &L, transition: &ComponentTransition) {
+    let report = &transition.report;
+
+    let mut record = logger.create_log_record();
+    record.set_event_name(TRANSITION_EVENT_NAME);
+    record.set_timestamp(SystemTime::now());
+    record.set_severity_number(severity(report.state()));
+    record.set_body(AnyValue::from(format!(
+        "component {} changed from {} to {}",
+        report.name(),
+        transition.old_state,
+        report.state()
+    )));
+
+    record.add_attribute("app_health.component", report.name().to_owned());
+    record.add_attribute("app_health.old_state", transition.old_state.to_string());
+    record.add_attribute("app_health.new_state", report.state().to_string());
+
+    if let Some(signal) = report.dominant_signal() {
+        record.add_attributes(signal.attributes().iter().map(|a| (Key::from(a.name()), AnyValue::from(a.value()))));
+    }
+
+    logger.emit(record);
+}
+
+const fn severity(state: Health) -> Severity {
+    match state {
+        Health::Nominal => Severity::Info,
+        Health::Degraded => Severity::Warn,
+        Health::Critical | Health::Down => Severity::Error,
+        Health::Unrecoverable => Severity::Fatal,
+    }
+}
+
+impl From<&AttributeString> for StringValue {
+    fn from(s: &AttributeString) -> Self {
+        match s {
+            AttributeString::Static(s) => Self::from(*s),
+            AttributeString::Boxed(s) => Self::from(s.to_string()),
+        }
+    }
+}
+
+impl From<&AttributeString> for Key {
+    fn from(s: &AttributeString) -> Self {
+        match s {
+            AttributeString::Static(s) => Self::from_static_str(s),
+            AttributeString::Boxed(s) => Self::from(s.to_string()),
+        }
+    }
+}
+
+impl From<&AttributeValue> for Value {
+    fn from(v: &AttributeValue) -> Self {
+        match v {
+            AttributeValue::Int(i) => Self::I64(*i),
+            AttributeValue::Double(d) => Self::F64(*d),
+            AttributeValue::String(s) => Self::String(s.into()),
+            AttributeValue::Boolean(b) => Self::Bool(*b),
+        }
+    }
+}
+
+impl From<&AttributeValue> for AnyValue {
+    fn from(v: &AttributeValue) -> Self {
+        match v {
+            AttributeValue::Int(i) => Self::Int(*i),
+            AttributeValue::Double(d) => Self::Double(*d),
+            AttributeValue::String(s) => Self::String(s.into()),
+            AttributeValue::Boolean(b) => Self::Boolean(*b),
+        }
+    }
+}
+
+impl From<&Attribute> for KeyValue {
+    fn from(a: &Attribute) -> Self {
+        Self::new(a.name(), a.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use opentelemetry::logs::LoggerProvider as _;
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider};
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics, ScopeMetrics};
+    use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+    use tokio::time::timeout;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn attribute_conversions_are_lossless() {
+        let attribute = Attribute::from(("latency_ms", 12.5));
+        let kv = KeyValue::from(&attribute);
+        assert_eq!(kv.key.as_str(), "latency_ms");
+        assert_eq!(kv.value, Value::F64(12.5));
+
+        assert_eq!(Value::from(&AttributeValue::Int(i64::MIN)), Value::I64(i64::MIN));
+        assert_eq!(Value::from(&AttributeValue::Boolean(true)), Value::Bool(true));
+        assert_eq!(Value::from(&AttributeValue::from("x")), Value::from("x"));
+        assert_eq!(AnyValue::from(&AttributeValue::from(String::from("y"))), AnyValue::from("y"));
+        assert_eq!(AnyValue::from(&AttributeValue::Int(7)), AnyValue::Int(7));
+    }
+
+    #[tokio::test]
+    async fn reports_gauges_and_transitions() {
+        let aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let log_exporter = InMemoryLogExporter::default();
+        let logger_provider = SdkLoggerProvider::builder().with_simple_exporter(log_exporter.clone()).build();
+
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(metric_exporter.clone()).build())
+            .build();
+
+        let mut component = aggregator.component("database");
+        let _instrumentation = OtelInstrumentation::new(&aggregator, &meter_provider.meter("test"), logger_provider.logger("test"));
+
+        let mut publisher = component.publisher();
+        publisher.publish(Health::Degraded, [("latency_ms", 250_i64)]);
+        timeout(WAIT, component.changed()).await.unwrap().unwrap();
+
+        // wait for the transition to be logged
+        let logs = timeout(WAIT, async {
+            loop {
+                let logs = log_exporter.get_emitted_logs().unwrap();
+                if !logs.is_empty() {
+                    return logs;
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let record = &logs[0].record;
+        assert_eq!(record.event_name(), Some(TRANSITION_EVENT_NAME));
+        let attributes: Vec<_> = record.attributes_iter().cloned().collect();
+        assert!(attributes.contains(&(Key::from("app_health.component"), AnyValue::from("database"))));
+        assert!(attributes.contains(&(Key::from("app_health.new_state"), AnyValue::from("Degraded"))));
+        assert!(attributes.contains(&(Key::from("latency_ms"), AnyValue::Int(250))));
+
+        // wait for the per-component snapshot to catch up
+        timeout(
+            WAIT,
+            wait_for_gauge(&meter_provider, &metric_exporter, "database", Health::Degraded),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn refreshes_components_that_leave_overall_health_unchanged() {
+        let aggregator = Aggregator::builder()
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(metric_exporter.clone()).build())
+            .build();
+
+        let database = aggregator.component("database");
+        let cache = aggregator.component("cache");
+        let logger = SdkLoggerProvider::builder().build().logger("test");
+        let _instrumentation = OtelInstrumentation::new(&aggregator, &meter_provider.meter("test"), logger);
+
+        let mut database_publisher = database.publisher();
+        database_publisher.publish(Health::Critical, [("reason", "unreachable")]);
+        timeout(
+            WAIT,
+            wait_for_gauge(&meter_provider, &metric_exporter, "database", Health::Critical),
+        )
+        .await
+        .unwrap();
+
+        // the overall health stays critical, but the cache's gauge must still follow along
+        let mut cache_publisher = cache.publisher();
+        cache_publisher.publish(Health::Degraded, [("reason", "slow")]);
+        timeout(WAIT, wait_for_gauge(&meter_provider, &metric_exporter, "cache", Health::Degraded))
+            .await
+            .unwrap();
+        assert_eq!(aggregator.state(), Health::Critical);
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn works_without_tokio() {
+        let aggregator = Aggregator::builder()
+            .runtime(crate::ThreadRuntime)
+            .debounce_interval(Duration::ZERO)
+            .component_debounce_interval(Duration::ZERO)
+            .build();
+
+        let log_exporter = InMemoryLogExporter::default();
+        let logger_provider = SdkLoggerProvider::builder().with_simple_exporter(log_exporter.clone()).build();
+        let meter = SdkMeterProvider::default().meter("test");
+
+        let component = aggregator.component("database");
+        let _instrumentation = OtelInstrumentation::new(&aggregator, &meter, logger_provider.logger("test"));
+
+        let mut publisher = component.publisher();
+        publisher.publish(Health::Degraded, [("reason", "slow")]);
+
+        let started = std::time::Instant::now();
+        while log_exporter.get_emitted_logs().unwrap().is_empty() {
+            assert!(started.elapsed() < WAIT, "the transition was never logged");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Wait until the per-component gauge reports the given state for a component.
+    async fn wait_for_gauge(meter_provider: &SdkMeterProvider, exporter: &InMemoryMetricExporter, name: &str, state: Health) {
+        let expected = KeyValue::new("app_health.component", name.to_owned());
+        loop {
+            meter_provider.force_flush().unwrap();
+            let metrics = exporter.get_finished_metrics().unwrap();
+            let found = metrics
+                .iter()
+                .flat_map(ResourceMetrics::scope_metrics)
+                .flat_map(ScopeMetrics::metrics)
+                .filter(|m| m.name() == "app_health.component.state")
+                .any(|m| match m.data() {
+                    AggregatedMetrics::I64(MetricData::Gauge(gauge)) => gauge
+                        .data_points()
+                        .any(|dp| dp.value() == state as i64 && dp.attributes().any(|a| *a == expected)),
+                    _ => false,
+                });
+
+            if found {
+                return;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use core::fmt::Display;
+
+/// The outcome of a liveness or readiness probe.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProbeStatus {
+    /// The probe succeeds.
+    #[default]
+    Pass,
+
+    /// The probe fails.
+    Fail,
+
+    /// The application or some of its components are still in their startup grace period.
+    ///
+    /// Only the readiness probe reports this status, see
+    /// [`AggregatorBuilder::startup_grace_period`](crate::AggregatorBuilder::startup_grace_period). Like
+    /// [`Fail`](Self::Fail), it means the application shouldn't receive traffic yet.
+    Starting,
+}
+
+impl ProbeStatus {
+    /// Returns `true` if the probe succeeds.
+    #[must_use]
+    pub const fn is_pass(self) -> bool {
+        matches!(self, Self::Pass)
+    }
+}
+
+impl Display for ProbeStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Self::Pass => "Pass",
+            Self::Fail => "Fail",
+            Self::Starting => "Starting",
+        };
+
+        f.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProbeStatus;
+
+    #[test]
+    fn display_strings() {
+        assert_eq!(ProbeStatus::Pass.to_string(), "Pass");
+        assert_eq!(ProbeStatus::Fail.to_string(), "Fail");
+        assert_eq!(ProbeStatus::Starting.to_string(), "Starting");
+    }
+}
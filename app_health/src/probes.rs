@@ -0,0 +1,25 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// Identifies the probes a component contributes to.
+    ///
+    /// An aggregator maintains a liveness and a readiness view of the application, in addition to its overall health.
+    /// Each view is computed only from the components that contribute to it. For example, a component
+    /// that warms up a cache at startup should block readiness while it's still warming, but it should never cause
+    /// the application to be restarted, so it contributes to readiness alone.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Probes: u32 {
+        /// The component contributes to [`Aggregator::liveness`](crate::Aggregator::liveness).
+        const LIVENESS = 1 << 0;
+
+        /// The component contributes to [`Aggregator::readiness`](crate::Aggregator::readiness).
+        const READINESS = 1 << 1;
+    }
+}
+
+impl Default for Probes {
+    fn default() -> Self {
+        Self::all()
+    }
+}
@@ -0,0 +1,362 @@
+use crate::health::ALL_HEALTH_STATES;
+use crate::{Health, Report};
+use core::fmt::Write;
+use std::collections::BTreeMap;
+
+const DEFAULT_MAX_SERIES_PER_COMPONENT: usize = 10;
+
+/// Renders health reports in the Prometheus text exposition format.
+///
+/// The output contains the following gauges:
+///
+/// - `app_health_state`: The overall health of the application, from `0` (`Nominal`) to `4` (`Unrecoverable`).
+/// - `app_health_component_state`: One series per component per health state, set to `1` for the component's current
+///   state and `0` otherwise.
+/// - `app_health_signal_count`: One series per component per health state, holding the number of publisher signals in
+///   that state as returned by [`Report::signal_count`].
+///
+/// Child components are rendered as components of their own, using their dotted names.
+///
+/// Attributes of publisher signals can optionally be turned into labels with [`attribute_labels`](Self::attribute_labels).
+/// In that case, the output also contains an `app_health_signal` gauge with one series per distinct combination of
+/// component, state, and selected attribute values, holding the number of publishers reporting that combination.
+/// To bound cardinality, at most [`max_series_per_component`](Self::max_series_per_component) such series are produced
+/// for each component. Any excess is counted in `app_health_signal_series_dropped`. These series are built from the
+/// signals present in the reports, so the reports must be gathered with a [`Filter`](crate::Filter) that includes them.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Filter, PrometheusExporter};
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let _database = aggregator.component("database");
+///
+///     let exporter = PrometheusExporter::new().attribute_labels(["reason"]);
+///     if let Some(reports) = aggregator.reports(Filter::ALL).await {
+///         let text = exporter.render(aggregator.state(), reports);
+///         println!("{text}");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrometheusExporter {
+    // each attribute name along with the label name it's exported as
+    attribute_labels: Vec<(Box<str>, Box<str>)>,
+    max_series_per_component: usize,
+}
+
+impl PrometheusExporter {
+    /// Create an exporter that doesn't turn any attributes into labels.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            attribute_labels: Vec::new(),
+            max_series_per_component: DEFAULT_MAX_SERIES_PER_COMPONENT,
+        }
+    }
+
+    /// Set the names of the signal attributes that are turned into labels.
+    ///
+    /// Characters that aren't valid in a Prometheus label name are replaced with underscores, and leading double
+    /// underscores are collapsed since Prometheus reserves them. Attributes named `component` or `state` are ignored,
+    /// since those labels are reserved by the exporter. When several attributes end up with the same label name, only
+    /// the first of them is exported.
+    #[must_use]
+    pub fn attribute_labels<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.attribute_labels.clear();
+        for name in names {
+            let label = label_name(name.as_ref());
+            if matches!(label.as_str(), "component" | "state") || self.attribute_labels.iter().any(|(_, l)| **l == *label) {
+                continue;
+            }
+
+            self.attribute_labels.push((Box::from(name.as_ref()), label.into()));
+        }
+
+        self
+    }
+
+    /// Set the maximum number of `app_health_signal` series produced for each component.
+    ///
+    /// Defaults to 10.
+    #[must_use]
+    pub const fn max_series_per_component(mut self, max: usize) -> Self {
+        self.max_series_per_component = max;
+        self
+    }
+
+    /// Render the overall health of the application along with its component reports.
+    #[must_use]
+    pub fn render(&self, overall: Health, reports: impl IntoIterator<Item = Report>) -> String {
+        let mut flattened = Vec::new();
+        flatten(reports, &mut flattened);
+        let reports = flattened;
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "app_health_state",
+            "Overall health of the application (0=Nominal, 4=Unrecoverable).",
+        );
+        let _ = writeln!(out, "app_health_state {}", overall as u8);
+
+        header(
+            &mut out,
+            "app_health_component_state",
+            "Whether a component is in a given health state.",
+        );
+        for report in &reports {
+            for state in ALL_HEALTH_STATES {
+                let value = u8::from(report.state() == state);
+                let _ = writeln!(
+                    out,
+                    "app_health_component_state{{component=\"{}\",state=\"{}\"}} {value}",
+                    escape(report.name()),
+                    state_label(state)
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "app_health_signal_count",
+            "Number of publisher signals per component and health state.",
+        );
+        for report in &reports {
+            for state in ALL_HEALTH_STATES {
+                let _ = writeln!(
+                    out,
+                    "app_health_signal_count{{component=\"{}\",state=\"{}\"}} {}",
+                    escape(report.name()),
+                    state_label(state),
+                    report.signal_count(state)
+                );
+            }
+        }
+
+        if !self.attribute_labels.is_empty() {
+            self.render_signals(&mut out, &reports);
+        }
+
+        out
+    }
+
+    fn render_signals(&self, out: &mut String, reports: &[Report]) {
+        header(
+            out,
+            "app_health_signal",
+            "Number of publishers reporting a signal with the given attributes.",
+        );
+
+        let mut dropped = Vec::new();
+        for report in reports {
+            // collapse signals that only differ by attributes that aren't exported
+            let mut series: BTreeMap<(Health, Vec<String>), usize> = BTreeMap::new();
+            for state in ALL_HEALTH_STATES {
+                for (signal, count) in report.signals(state) {
+                    let values = self
+                        .attribute_labels
+                        .iter()
+                        .map(|(name, _)| {
+                            signal
+                                .attributes()
+                                .iter()
+                                .find(|a| a.name().as_str() == name.as_ref())
+                                .map(|a| a.value().to_string())
+                                .unwrap_or_default()
+                        })
+                        .collect();
+
+                    *series.entry((state, values)).or_default() += count;
+                }
+            }
+
+            let excess = series.len().saturating_sub(self.max_series_per_component);
+            if excess > 0 {
+                dropped.push((report.name(), excess));
+            }
+
+            for ((state, values), count) in series.into_iter().take(self.max_series_per_component) {
+                let _ = write!(
+                    out,
+                    "app_health_signal{{component=\"{}\",state=\"{}\"",
+                    escape(report.name()),
+                    state_label(state)
+                );
+
+                for ((_, label), value) in self.attribute_labels.iter().zip(values) {
+                    let _ = write!(out, ",{label}=\"{}\"", escape(&value));
+                }
+
+                let _ = writeln!(out, "}} {count}");
+            }
+        }
+
+        header(
+            out,
+            "app_health_signal_series_dropped",
+            "Number of app_health_signal series omitted to limit cardinality.",
+        );
+        for (name, excess) in dropped {
+            let _ = writeln!(out, "app_health_signal_series_dropped{{component=\"{}\"}} {excess}", escape(name));
+        }
+    }
+}
+
+impl Default for PrometheusExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+}
+
+const fn state_label(state: Health) -> &'static str {
+    match state {
+        Health::Nominal => "nominal",
+        Health::Degraded => "degraded",
+        Health::Critical => "critical",
+        Health::Down => "down",
+        Health::Unrecoverable => "unrecoverable",
+    }
+}
+
+/// Turn an attribute name into a valid Prometheus label name.
+fn label_name(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+
+    // names starting with two underscores are reserved for Prometheus' own use
+    while result.starts_with("__") {
+        let _ = result.remove(0);
+    }
+
+    result
+}
+
+/// Escape a label value according to the exposition format.
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Collect reports along with the reports of all their descendants.
+fn flatten(reports: impl IntoIterator<Item = Report>, out: &mut Vec<Report>) {
+    for mut report in reports {
+        let children = core::mem::take(&mut report.children);
+        out.push(report);
+        flatten(children, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::Signal;
+
+    fn report(name: &str, state: Health, signals: &[(Signal, usize)]) -> Report {
+        let mut report = Report {
+            name: name.into(),
+            state,
+            ..Report::default()
+        };
+
+        for (signal, count) in signals {
+            report.counts[signal.state() as usize] += count;
+            report.signals[signal.state() as usize].push((signal.clone(), *count));
+        }
+
+        report
+    }
+
+    #[test]
+    fn renders_states_and_counts() {
+        let signals = [(Signal::new(Health::Degraded, [("reason", "slow")]), 2)];
+        let text = PrometheusExporter::new().render(Health::Degraded, [report("db", Health::Degraded, &signals)]);
+
+        assert!(text.contains("app_health_state 1\n"));
+        assert!(text.contains("app_health_component_state{component=\"db\",state=\"nominal\"} 0\n"));
+        assert!(text.contains("app_health_component_state{component=\"db\",state=\"degraded\"} 1\n"));
+        assert!(text.contains("app_health_signal_count{component=\"db\",state=\"degraded\"} 2\n"));
+        assert!(text.contains("app_health_signal_count{component=\"db\",state=\"down\"} 0\n"));
+        assert!(!text.contains("app_health_signal{"));
+    }
+
+    #[test]
+    fn renders_attribute_labels() {
+        let signals = [
+            (Signal::new(Health::Degraded, [("reason", "slow"), ("host", "a")]), 1),
+            (Signal::new(Health::Degraded, [("reason", "slow"), ("host", "b")]), 2),
+            (Signal::new(Health::Critical, [("reason", "say \"hi\"")]), 1),
+        ];
+
+        let text = PrometheusExporter::new()
+            .attribute_labels(["reason", "state"])
+            .render(Health::Critical, [report("db", Health::Critical, &signals)]);
+
+        assert!(text.contains("app_health_signal{component=\"db\",state=\"degraded\",reason=\"slow\"} 3\n"));
+        assert!(text.contains("app_health_signal{component=\"db\",state=\"critical\",reason=\"say \\\"hi\\\"\"} 1\n"));
+    }
+
+    #[test]
+    fn limits_cardinality() {
+        let signals: Vec<_> = (0..5)
+            .map(|i| (Signal::new(Health::Degraded, [("host", format!("host-{i}"))]), 1))
+            .collect();
+
+        let text = PrometheusExporter::new()
+            .attribute_labels(["host"])
+            .max_series_per_component(2)
+            .render(Health::Degraded, [report("db", Health::Degraded, &signals)]);
+
+        assert_eq!(text.matches("app_health_signal{").count(), 2);
+        assert!(text.contains("app_health_signal_series_dropped{component=\"db\"} 3\n"));
+    }
+
+    #[test]
+    fn sanitizes_label_names() {
+        assert_eq!(label_name("http.status-code"), "http_status_code");
+        assert_eq!(label_name("1st"), "_1st");
+        assert_eq!(label_name(""), "_");
+        assert_eq!(label_name("__name__"), "_name__");
+        assert_eq!(label_name(".."), "_");
+    }
+
+    #[test]
+    fn skips_colliding_label_names() {
+        let signals = [(Signal::new(Health::Degraded, [("http.status", "500"), ("http_status", "503")]), 1)];
+
+        let text = PrometheusExporter::new()
+            .attribute_labels(["http.status", "http_status", "__name__"])
+            .render(Health::Degraded, [report("db", Health::Degraded, &signals)]);
+
+        assert!(text.contains("app_health_signal{component=\"db\",state=\"degraded\",http_status=\"500\",_name__=\"\"} 1\n"));
+        assert!(!text.contains("http_status=\"503\""));
+        assert!(!text.contains(",__"));
+    }
+}
@@ -1,9 +1,14 @@
 use crate::Attribute;
 use crate::Health;
+use crate::Runtime;
 use crate::component::ComponentMessage;
+use crate::drain_guard::DrainGuard;
 use crate::signal::Signal;
+use crate::sync::mpsc;
 use core::mem::replace;
-use tokio::sync::mpsc;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use std::sync::Arc;
 
 /// A publisher represents a single source of health information for a component,
 ///
@@ -22,6 +27,10 @@ use tokio::sync::mpsc;
 /// causing a problem, or any other metadata useful during diagnostics. The attributes are collected and made available in health
 /// reports, aiding in troubleshooting and understanding the health of the component and application.
 ///
+/// Publishers created with [`publisher_with_ttl`](crate::Component::publisher_with_ttl) must publish or call
+/// [`heartbeat`](Self::heartbeat) regularly, otherwise the component considers them stale. This catches publishers whose
+/// thread is stuck and which would otherwise keep reporting their last signal forever.
+///
 /// # Example
 ///
 /// ```no_run
@@ -61,22 +70,55 @@ use tokio::sync::mpsc;
 pub struct Publisher {
     signal: Signal,
     component_tx: mpsc::WeakUnboundedSender<ComponentMessage>,
+    lease: Option<(u64, Duration)>,
+    drain_guard: DrainGuard,
+    runtime: Arc<dyn Runtime>,
 }
 
+/// Source of unique identifiers for publishers with a time-to-live.
+static NEXT_LEASE_ID: AtomicU64 = AtomicU64::new(0);
+
 impl Publisher {
     /// Creates a new component.
     #[must_use]
-    pub(crate) fn new(component_tx: mpsc::WeakUnboundedSender<ComponentMessage>) -> Self {
+    pub(crate) fn new(
+        component_tx: mpsc::WeakUnboundedSender<ComponentMessage>,
+        drain_guard: DrainGuard,
+        runtime: Arc<dyn Runtime>,
+    ) -> Self {
+        let result = Self {
+            signal: Signal::nominal(),
+            component_tx,
+            lease: None,
+            drain_guard,
+            runtime,
+        };
+
         // if initial registration fails, it means the component is somehow gone already
         // this implies that any attempt for this publisher to publish will fail, and that's OK
-        if let Some(channel) = component_tx.upgrade() {
-            let _ = channel.send(ComponentMessage::StartPublishing(Signal::nominal()));
-        }
+        result.send(ComponentMessage::StartPublishing(Signal::nominal()));
+        result
+    }
 
-        Self {
+    /// Creates a new publisher that goes stale unless it reports in within the given time-to-live.
+    #[must_use]
+    pub(crate) fn with_ttl(
+        component_tx: mpsc::WeakUnboundedSender<ComponentMessage>,
+        ttl: Duration,
+        drain_guard: DrainGuard,
+        runtime: Arc<dyn Runtime>,
+    ) -> Self {
+        let id = NEXT_LEASE_ID.fetch_add(1, Ordering::Relaxed);
+        let result = Self {
             signal: Signal::nominal(),
             component_tx,
-        }
+            lease: Some((id, ttl)),
+            drain_guard,
+            runtime,
+        };
+
+        result.send(ComponentMessage::StartLease(id, ttl, Signal::nominal()));
+        result
     }
 
     /// Get the publisher's current signal.
@@ -90,45 +132,87 @@ impl Publisher {
         self.change_signal(Signal::new(state, attributes));
     }
 
+    /// Whether the component this publisher reports to still exists.
+    pub(crate) fn is_connected(&self) -> bool {
+        self.component_tx.strong_count() > 0
+    }
+
+    /// Let the component know the publisher is still alive without changing its signal.
+    ///
+    /// This only matters for publishers created with [`publisher_with_ttl`](crate::Component::publisher_with_ttl),
+    /// it does nothing for other publishers. Publishing a signal also counts as a heartbeat.
+    pub fn heartbeat(&self) {
+        if let Some((id, _)) = self.lease {
+            self.send(ComponentMessage::RenewLease(id, None));
+        }
+    }
+
+    /// Send a message to the component's worker, giving the runtime a chance to process it right away.
+    ///
+    /// Failures are ignored, since they mean the component is dead already.
+    fn send(&self, msg: ComponentMessage) {
+        if let Some(channel) = self.component_tx.upgrade() {
+            let _ = channel.send(msg);
+            self.runtime.run_until_stalled();
+        }
+    }
+
     /// Send signal changes to the background worker.
     fn change_signal(&mut self, new_signal: Signal) {
-        if new_signal != self.signal {
+        let msg = if new_signal == self.signal {
+            // the signal keeps its original publication time, it's merely reaffirmed
+            self.signal.reaffirm(new_signal.last_reaffirmed());
+            match self.lease {
+                // every publication renews the lease, even if the signal is unchanged
+                Some((id, _)) => ComponentMessage::RenewLease(id, None),
+                None => ComponentMessage::ReaffirmSignal(self.signal.clone()),
+            }
+        } else {
             let old_signal = replace(&mut self.signal, new_signal);
-
-            // communicate the signal change to the component, but we don't care if it fails since it means the component is dead already
-            if let Some(channel) = self.component_tx.upgrade() {
-                let _ = channel.send(ComponentMessage::ChangeHealth(old_signal, self.signal.clone()));
+            match self.lease {
+                Some((id, _)) => ComponentMessage::RenewLease(id, Some(self.signal.clone())),
+                None => ComponentMessage::ChangeHealth(old_signal, self.signal.clone()),
             }
-        }
+        };
+
+        // communicate the signal change to the component, but we don't care if it fails since it means the component is dead already
+        self.send(msg);
     }
 }
 
 impl Clone for Publisher {
     /// Create a new publisher that starts in the [`Nominal`](Health::Nominal) state.
     fn clone(&self) -> Self {
-        Self::new(self.component_tx.clone())
+        match self.lease {
+            Some((_, ttl)) => Self::with_ttl(self.component_tx.clone(), ttl, self.drain_guard.clone(), Arc::clone(&self.runtime)),
+            None => Self::new(self.component_tx.clone(), self.drain_guard.clone(), Arc::clone(&self.runtime)),
+        }
     }
 }
 
 impl Drop for Publisher {
     fn drop(&mut self) {
         // try to tell the component about our demise, but we don't care if it fails since it means the component is dead already
-        if let Some(channel) = self.component_tx.upgrade() {
-            let _ = channel.send(ComponentMessage::StopPublishing(replace(&mut self.signal, Signal::nominal())));
-        }
+        let msg = match self.lease {
+            Some((id, _)) => ComponentMessage::StopLease(id),
+            None => ComponentMessage::StopPublishing(replace(&mut self.signal, Signal::nominal())),
+        };
+
+        self.send(msg);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TokioRuntime;
 
     #[test]
     fn test_publisher_clone() {
         let (tx, _rx) = mpsc::unbounded_channel();
         let weak_tx = tx.downgrade();
 
-        let publisher1 = Publisher::new(weak_tx);
+        let publisher1 = Publisher::new(weak_tx, DrainGuard::default(), Arc::new(TokioRuntime::new()));
         let publisher2 = publisher1.clone();
 
         assert_eq!(publisher1.signal().state(), Health::Nominal);
@@ -141,4 +225,53 @@ mod tests {
         assert_eq!(publisher1.signal().state(), Health::Degraded);
         assert_eq!(publisher2.signal().state(), Health::Nominal);
     }
+
+    #[test]
+    fn republishing_reaffirms_signal() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut publisher = Publisher::new(tx.downgrade(), DrainGuard::default(), Arc::new(TokioRuntime::new()));
+        assert!(matches!(rx.try_recv(), Ok(ComponentMessage::StartPublishing(_))));
+
+        publisher.publish(Health::Degraded, [("reason", "slow")]);
+        assert!(matches!(rx.try_recv(), Ok(ComponentMessage::ChangeHealth(..))));
+        let first_published = publisher.signal().first_published();
+
+        std::thread::sleep(Duration::from_millis(1));
+        publisher.publish(Health::Degraded, [("reason", "slow")]);
+        let Ok(ComponentMessage::ReaffirmSignal(signal)) = rx.try_recv() else {
+            panic!("expected the signal to be reaffirmed");
+        };
+
+        assert_eq!(signal.first_published(), first_published);
+        assert!(signal.last_reaffirmed() > first_published);
+        assert_eq!(publisher.signal().last_reaffirmed(), signal.last_reaffirmed());
+    }
+
+    #[test]
+    fn test_publisher_with_ttl_renews_lease() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut publisher = Publisher::with_ttl(
+            tx.downgrade(),
+            Duration::from_secs(1),
+            DrainGuard::default(),
+            Arc::new(TokioRuntime::new()),
+        );
+
+        let Ok(ComponentMessage::StartLease(id, ttl, _)) = rx.try_recv() else {
+            panic!("expected the lease to start");
+        };
+        assert_eq!(ttl, Duration::from_secs(1));
+
+        publisher.heartbeat();
+        assert!(matches!(rx.try_recv(), Ok(ComponentMessage::RenewLease(i, None)) if i == id));
+
+        // publishing the same signal again still renews the lease
+        publisher.publish(Health::Degraded, [("reason", "slow")]);
+        assert!(matches!(rx.try_recv(), Ok(ComponentMessage::RenewLease(i, Some(_))) if i == id));
+        publisher.publish(Health::Degraded, [("reason", "slow")]);
+        assert!(matches!(rx.try_recv(), Ok(ComponentMessage::RenewLease(i, None)) if i == id));
+
+        drop(publisher);
+        assert!(matches!(rx.try_recv(), Ok(ComponentMessage::StopLease(i)) if i == id));
+    }
 }
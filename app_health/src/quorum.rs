@@ -0,0 +1,89 @@
+use crate::{AggregationPolicy, Health, Report};
+
+/// An aggregation policy where the overall health is the best health shared by a minimum number of components.
+///
+/// In other words, the overall health is the best state `S` such that at least `required` components are
+/// in state `S` or better. This makes it possible to tolerate the failure of a few redundant components,
+/// such as cache shards, without impacting the overall health of the application.
+///
+/// Each component's health is capped according to its [`Criticality`](crate::Criticality).
+/// If there are fewer components than `required`, this policy behaves like [`WorstOf`](crate::WorstOf).
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Quorum};
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     // with five cache shards, one of them can fail without affecting overall health
+///     let aggregator = Aggregator::builder().policy(Quorum::new(4)).build();
+///     let _shards: Vec<_> = (0..5).map(|i| aggregator.component(format!("cache-{i}"))).collect();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Quorum {
+    required: usize,
+}
+
+impl Quorum {
+    /// Create a policy that requires at least `required` components to share a health state.
+    #[must_use]
+    pub const fn new(required: usize) -> Self {
+        Self { required }
+    }
+}
+
+impl AggregationPolicy for Quorum {
+    fn aggregate(&self, components: &[Report]) -> Health {
+        let mut states: Vec<Health> = components.iter().map(Report::effective_state).collect();
+        states.sort_unstable();
+
+        if self.required == 0 {
+            return Health::Nominal;
+        }
+
+        states.get(self.required - 1).or_else(|| states.last()).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reports(states: &[Health]) -> Vec<Report> {
+        states
+            .iter()
+            .map(|state| Report {
+                state: *state,
+                ..Report::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tolerates_minority_failures() {
+        let components = reports(&[Health::Nominal, Health::Down, Health::Nominal, Health::Nominal, Health::Nominal]);
+        assert_eq!(Quorum::new(4).aggregate(&components), Health::Nominal);
+    }
+
+    #[test]
+    fn reports_state_once_quorum_is_lost() {
+        let components = reports(&[Health::Nominal, Health::Down, Health::Degraded, Health::Nominal, Health::Nominal]);
+        assert_eq!(Quorum::new(4).aggregate(&components), Health::Degraded);
+        assert_eq!(Quorum::new(5).aggregate(&components), Health::Down);
+    }
+
+    #[test]
+    fn too_few_components_is_worst_of() {
+        let components = reports(&[Health::Nominal, Health::Critical]);
+        assert_eq!(Quorum::new(3).aggregate(&components), Health::Critical);
+        assert_eq!(Quorum::new(3).aggregate(&[]), Health::Nominal);
+    }
+
+    #[test]
+    fn zero_quorum_is_nominal() {
+        let components = reports(&[Health::Unrecoverable]);
+        assert_eq!(Quorum::new(0).aggregate(&components), Health::Nominal);
+    }
+}
@@ -1,19 +1,37 @@
 use crate::health::{ALL_HEALTH_STATES, NUM_HEALTH_STATES};
 use crate::signal::Signal;
-use crate::{Health, Signals};
+use crate::{Criticality, Escalation, Health, HealthOverride, Maintenance, Probes, Signals, Transition};
 use core::fmt::Display;
+use core::time::Duration;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 /// The health of a single application component.
 ///
 /// A component's health is determined by combining the data from the component's active publishers.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Report {
     pub(crate) name: Arc<str>,
     pub(crate) state: Health,
+    pub(crate) criticality: Criticality,
+    pub(crate) probes: Probes,
+    pub(crate) flapping: bool,
+    pub(crate) escalation: Option<Escalation>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339"))]
+    pub(crate) since: SystemTime,
     pub(crate) counts: [usize; NUM_HEALTH_STATES],
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339::array"))]
+    pub(crate) oldest_signals: [Option<SystemTime>; NUM_HEALTH_STATES],
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339::array"))]
+    pub(crate) newest_signals: [Option<SystemTime>; NUM_HEALTH_STATES],
     pub(crate) signals: [Vec<(Signal, usize)>; NUM_HEALTH_STATES],
+    pub(crate) history: Vec<Transition>,
+    pub(crate) children: Vec<Self>,
+    pub(crate) maintenance: Option<Maintenance>,
+    pub(crate) health_override: Option<HealthOverride>,
+    pub(crate) starting: bool,
+    pub(crate) published: bool,
 }
 
 impl Report {
@@ -25,18 +43,126 @@ impl Report {
 
     /// The overall health of the component.
     ///
-    /// A component's health is determined by the most severe health state reported by any active publisher.
+    /// A component's health is determined by the most severe health state reported by any active publisher, unless
+    /// it's forced by a [`health_override`](Self::health_override).
     #[must_use]
     pub const fn state(&self) -> Health {
         self.state
     }
 
+    /// How much the component is allowed to affect the overall health of the application.
+    #[must_use]
+    pub const fn criticality(&self) -> Criticality {
+        self.criticality
+    }
+
+    /// The probes the component contributes to.
+    #[must_use]
+    pub const fn probes(&self) -> Probes {
+        self.probes
+    }
+
+    /// The health the component contributes to the overall health of the application.
+    ///
+    /// This is the component's [`state`](Self::state), capped according to its [`criticality`](Self::criticality) and
+    /// any [`maintenance`](Self::maintenance) it's in.
+    #[must_use]
+    pub fn effective_state(&self) -> Health {
+        let state = self.criticality.cap(self.state);
+        self.maintenance.as_ref().map_or(state, |m| state.min(m.ceiling()))
+    }
+
+    /// The maintenance the component is in, if any.
+    ///
+    /// See [`Component::begin_maintenance`](crate::Component::begin_maintenance).
+    #[must_use]
+    pub const fn maintenance(&self) -> Option<&Maintenance> {
+        self.maintenance.as_ref()
+    }
+
+    /// Whether the component has been changing state too often recently.
+    ///
+    /// See [`ComponentBuilder::flap_detection`](crate::ComponentBuilder::flap_detection).
+    #[must_use]
+    pub const fn is_flapping(&self) -> bool {
+        self.flapping
+    }
+
+    /// The escalation rule that raised the component's state, if any.
+    ///
+    /// See [`ComponentBuilder::escalate`](crate::ComponentBuilder::escalate).
+    #[must_use]
+    pub const fn escalation(&self) -> Option<Escalation> {
+        self.escalation
+    }
+
+    /// When the component entered its current state.
+    ///
+    /// For a component that has never changed state, this is when it was created.
+    #[must_use]
+    pub const fn since(&self) -> SystemTime {
+        self.since
+    }
+
+    /// How long ago the longest-standing active signal in the given health state was first published.
+    ///
+    /// This returns `None` if there are no active signals in the given state. Unlike [`signals`](Self::signals), this
+    /// doesn't depend on the filter the report was gathered with.
+    #[must_use]
+    pub fn oldest_signal_age(&self, state: Health) -> Option<Duration> {
+        self.oldest_signals[state as usize].map(age)
+    }
+
+    /// How long ago the most recent active signal in the given health state was first published.
+    ///
+    /// This returns `None` if there are no active signals in the given state. Unlike [`signals`](Self::signals), this
+    /// doesn't depend on the filter the report was gathered with.
+    #[must_use]
+    pub fn newest_signal_age(&self, state: Health) -> Option<Duration> {
+        self.newest_signals[state as usize].map(age)
+    }
+
+    /// The operator override forcing the component's state, if any.
+    ///
+    /// See [`Component::override_health`](crate::Component::override_health).
+    #[must_use]
+    pub const fn health_override(&self) -> Option<&HealthOverride> {
+        self.health_override.as_ref()
+    }
+
+    /// Whether the component is still in its startup grace period.
+    ///
+    /// Starting components don't count toward the liveness probe, and make the readiness probe report
+    /// [`ProbeStatus::Starting`](crate::ProbeStatus::Starting). See
+    /// [`ComponentBuilder::startup_grace_period`](crate::ComponentBuilder::startup_grace_period).
+    #[must_use]
+    pub const fn is_starting(&self) -> bool {
+        self.starting
+    }
+
+    /// Whether any of the component's publishers has published a signal since the component was created.
+    #[must_use]
+    pub const fn has_published(&self) -> bool {
+        self.published
+    }
+
     /// The number of active publisher signals in the given health state.
     #[must_use]
     pub const fn signal_count(&self, state: Health) -> usize {
         self.counts[state as usize]
     }
 
+    /// The most common signal in the component's current state.
+    ///
+    /// This returns `None` if the report doesn't include the signals in the component's current state.
+    #[must_use]
+    pub fn dominant_signal(&self) -> Option<&Signal> {
+        self.signals[self.state as usize]
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(signal, _)| signal)
+    }
+
     /// The active publisher signals in the given health state, along with their counts.
     ///
     /// The count indicates how many publishers are reporting the same signal.
@@ -44,11 +170,60 @@ impl Report {
     pub fn signals(&self, state: Health) -> Signals<'_> {
         Signals::new(&self.signals[state as usize])
     }
+
+    /// The component's recent state transitions, oldest first.
+    ///
+    /// This is empty unless the report was gathered with [`Filter::HISTORY`](crate::Filter::HISTORY).
+    #[must_use]
+    pub fn history(&self) -> &[Transition] {
+        &self.history
+    }
+
+    /// Reports for the component's children, created with [`Component::child`](crate::Component::child).
+    ///
+    /// This is empty in the summaries handed to an [`AggregationPolicy`](crate::AggregationPolicy), since a child's
+    /// health is already rolled up into its parent's state.
+    #[must_use]
+    pub fn children(&self) -> &[Self] {
+        &self.children
+    }
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self {
+            name: Arc::default(),
+            state: Health::default(),
+            criticality: Criticality::default(),
+            probes: Probes::default(),
+            flapping: false,
+            escalation: None,
+            since: SystemTime::UNIX_EPOCH,
+            counts: [0; NUM_HEALTH_STATES],
+            oldest_signals: [None; NUM_HEALTH_STATES],
+            newest_signals: [None; NUM_HEALTH_STATES],
+            signals: Default::default(),
+            history: Vec::new(),
+            children: Vec::new(),
+            maintenance: None,
+            health_override: None,
+            starting: false,
+            published: false,
+        }
+    }
 }
 
 impl Display for Report {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Component {}: {}", self.name(), self.state())?;
+        if let Some(health_override) = &self.health_override {
+            write!(f, " ({health_override})")?;
+        }
+
+        if self.maintenance.is_some() {
+            write!(f, " (in maintenance)")?;
+        }
+
         for state in ALL_HEALTH_STATES {
             let signals = self.signals(state);
             if signals.len() > 0 {
@@ -62,3 +237,8 @@ impl Display for Report {
         Ok(())
     }
 }
+
+/// The time elapsed since the given time, or zero if it's in the future.
+fn age(time: SystemTime) -> Duration {
+    time.elapsed().unwrap_or_default()
+}
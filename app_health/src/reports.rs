@@ -1,19 +1,131 @@
-use crate::Report;
-use core::fmt::{Debug, Formatter};
+use crate::health::ALL_HEALTH_STATES;
+use crate::{HealthOverride, Report, flowchart};
+use core::fmt::{Debug, Formatter, Write};
 use std::vec::IntoIter;
 
 /// An iterator over component reports.
 pub struct Reports {
     iter: IntoIter<Report>,
+    health_override: Option<HealthOverride>,
 }
 
 impl Reports {
     #[must_use]
     pub(crate) fn new(reports: Vec<Report>) -> Self {
-        Self { iter: reports.into_iter() }
+        Self {
+            iter: reports.into_iter(),
+            health_override: None,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_override(mut self, health_override: Option<HealthOverride>) -> Self {
+        self.health_override = health_override;
+        self
+    }
+
+    /// The operator override forcing the application's overall health at the time of the reports, if any.
+    ///
+    /// See [`Aggregator::override_health`](crate::Aggregator::override_health).
+    #[must_use]
+    pub const fn health_override(&self) -> Option<&HealthOverride> {
+        self.health_override.as_ref()
+    }
+
+    /// Render the remaining reports as a Mermaid flowchart.
+    ///
+    /// Each component is drawn as a node colored according to its health, and labeled with its name, its state,
+    /// and the number of publisher signals in each state as returned by [`Report::signal_count`]. Child components
+    /// are linked from their parent.
+    ///
+    /// The output can be pasted into anything that renders Mermaid, such as GitHub comments.
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        let nodes = flatten(self.iter.as_slice());
+        let mut out = flowchart::start();
+
+        for (i, (report, _)) in nodes.iter().enumerate() {
+            flowchart::node(&mut out, i, &label(report).each_ref().map(String::as_str));
+        }
+
+        for (i, (_, parent)) in nodes.iter().enumerate() {
+            if let Some(parent) = parent {
+                flowchart::edge(&mut out, *parent, i);
+            }
+        }
+
+        for (i, (report, _)) in nodes.iter().enumerate() {
+            flowchart::style(&mut out, i, Some(report.state()));
+        }
+
+        out
+    }
+
+    /// Render the remaining reports as a Graphviz DOT graph.
+    ///
+    /// The graph holds the same information as [`to_mermaid`](Self::to_mermaid).
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let nodes = flatten(self.iter.as_slice());
+        let mut out = String::from("digraph health {\n    node [shape=box, style=filled, fontcolor=black];\n");
+
+        for (i, (report, _)) in nodes.iter().enumerate() {
+            let label: Vec<String> = label(report)
+                .iter()
+                .map(|line| line.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', " "))
+                .collect();
+
+            let _ = writeln!(
+                out,
+                "    c{i} [label=\"{}\", fillcolor=\"{}\"];",
+                label.join("\\n"),
+                report.state().color()
+            );
+        }
+
+        for (i, (_, parent)) in nodes.iter().enumerate() {
+            if let Some(parent) = parent {
+                let _ = writeln!(out, "    c{parent} -> c{i};");
+            }
+        }
+
+        out.push_str("}\n");
+        out
     }
 }
 
+/// List the reports along with all their descendants, each with the index of its parent.
+fn flatten(reports: &[Report]) -> Vec<(&Report, Option<usize>)> {
+    fn visit<'a>(reports: &'a [Report], parent: Option<usize>, nodes: &mut Vec<(&'a Report, Option<usize>)>) {
+        for report in reports {
+            let index = nodes.len();
+            nodes.push((report, parent));
+            visit(report.children(), Some(index), nodes);
+        }
+    }
+
+    let mut nodes = Vec::new();
+    visit(reports, None, &mut nodes);
+    nodes
+}
+
+/// Build the lines of text labeling a component's node.
+fn label(report: &Report) -> [String; 3] {
+    let counts: Vec<String> = ALL_HEALTH_STATES
+        .iter()
+        .filter(|state| report.signal_count(**state) > 0)
+        .map(|state| format!("{} {state}", report.signal_count(*state)))
+        .collect();
+
+    let counts = if counts.is_empty() {
+        "no signals".to_string()
+    } else {
+        counts.join(", ")
+    };
+
+    [report.name().to_owned(), report.state().to_string(), counts]
+}
+
 impl Iterator for Reports {
     type Item = Report;
 
@@ -45,7 +157,10 @@ impl ExactSizeIterator for Reports {
 
 impl Clone for Reports {
     fn clone(&self) -> Self {
-        Self { iter: self.iter.clone() }
+        Self {
+            iter: self.iter.clone(),
+            health_override: self.health_override.clone(),
+        }
     }
 }
 
@@ -54,3 +169,53 @@ impl Debug for Reports {
         self.iter.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Health;
+
+    fn reports() -> Reports {
+        let mut shard = Report {
+            name: "storage.shard-3".into(),
+            state: Health::Down,
+            ..Report::default()
+        };
+        shard.counts[Health::Down as usize] = 2;
+        shard.counts[Health::Nominal as usize] = 1;
+
+        let storage = Report {
+            name: "storage".into(),
+            state: Health::Down,
+            children: vec![shard],
+            ..Report::default()
+        };
+
+        let api = Report {
+            name: "\"api\"".into(),
+            ..Report::default()
+        };
+
+        Reports::new(vec![storage, api])
+    }
+
+    #[test]
+    fn renders_mermaid() {
+        let mermaid = reports().to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("c1[\"storage.shard-3<br/>Down<br/>1 Nominal, 2 Down\"]"));
+        assert!(mermaid.contains("c2[\"#quot;api#quot;<br/>Nominal<br/>no signals\"]"));
+        assert!(mermaid.contains("c0 --> c1"));
+        assert!(mermaid.contains("style c1 fill:#f44336"));
+    }
+
+    #[test]
+    fn renders_dot() {
+        let dot = reports().to_dot();
+        assert!(dot.starts_with("digraph health {"));
+        assert!(dot.contains("c1 [label=\"storage.shard-3\\nDown\\n1 Nominal, 2 Down\", fillcolor=\"#f44336\"];"));
+        assert!(dot.contains("c2 [label=\"\\\"api\\\"\\nNominal\\nno signals\""));
+        assert!(dot.contains("c0 -> c1;"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Serialization of timestamps as RFC 3339 strings, for use with `#[serde(with = "crate::rfc3339")]`.
+//!
+//! Crates such as `humantime` panic on timestamps before the Unix epoch and only accept UTC, so this covers the full
+//! range of years from 0000 to 9999 that RFC 3339 allows, along with UTC offsets when parsing.
+
+use core::time::Duration;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer, ser};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format(*time).ok_or_else(|| out_of_range::<S::Error>(*time))?)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse(&s).ok_or_else(|| D::Error::custom(format!("invalid RFC 3339 timestamp: {s}")))
+}
+
+/// The error reported for a timestamp outside of the years 0000 to 9999 that RFC 3339 can represent.
+fn out_of_range<E: ser::Error>(time: SystemTime) -> E {
+    E::custom(format_args!("timestamp {time:?} is outside of the range of RFC 3339"))
+}
+
+/// Serialization of an array of optional timestamps, one per health state.
+pub mod array {
+    use super::{format, out_of_range, parse};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::SystemTime;
+
+    pub fn serialize<S: Serializer, const N: usize>(times: &[Option<SystemTime>; N], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut strings = Vec::with_capacity(N);
+        for time in times {
+            strings.push(
+                time.map(|time| format(time).ok_or_else(|| out_of_range::<S::Error>(time)))
+                    .transpose()?,
+            );
+        }
+
+        strings.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[Option<SystemTime>; N], D::Error> {
+        let strings = Vec::<Option<String>>::deserialize(deserializer)?;
+        if strings.len() != N {
+            return Err(D::Error::invalid_length(strings.len(), &"one timestamp per health state"));
+        }
+
+        let mut times = [None; N];
+        for (time, s) in times.iter_mut().zip(strings) {
+            if let Some(s) = s {
+                *time = Some(parse(&s).ok_or_else(|| D::Error::custom(format!("invalid RFC 3339 timestamp: {s}")))?);
+            }
+        }
+
+        Ok(times)
+    }
+}
+
+/// Format a timestamp in UTC, such as `2024-05-01T12:30:00.25Z`.
+///
+/// This returns `None` for timestamps outside of the years 0000 to 9999, which RFC 3339 can't represent.
+pub fn format(time: SystemTime) -> Option<String> {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => (i64::try_from(d.as_secs()).unwrap_or(i64::MAX), d.subsec_nanos()),
+        Err(e) => {
+            let d = e.duration();
+            let secs = i64::try_from(d.as_secs()).map_or(i64::MIN, |s| -s);
+            match d.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs - 1, 1_000_000_000 - nanos),
+            }
+        }
+    };
+
+    let (year, month, day) = civil_from_days(secs.div_euclid(SECONDS_PER_DAY));
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    let secs = secs.rem_euclid(SECONDS_PER_DAY);
+    let mut out = format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    );
+
+    if nanos > 0 {
+        let fraction = format!("{nanos:09}");
+        out.push('.');
+        out.push_str(fraction.trim_end_matches('0'));
+    }
+
+    out.push('Z');
+    Some(out)
+}
+
+/// Parse a timestamp such as `2024-05-01T12:30:00.25Z` or `2024-05-01T14:30:00+02:00`.
+pub fn parse(s: &str) -> Option<SystemTime> {
+    let [
+        y1,
+        y2,
+        y3,
+        y4,
+        b'-',
+        mo1,
+        mo2,
+        b'-',
+        d1,
+        d2,
+        b'T' | b't' | b' ',
+        h1,
+        h2,
+        b':',
+        mi1,
+        mi2,
+        b':',
+        s1,
+        s2,
+        rest @ ..,
+    ] = s.as_bytes()
+    else {
+        return None;
+    };
+
+    let year = number(&[*y1, *y2, *y3, *y4])?;
+    let month = number(&[*mo1, *mo2])?;
+    let day = number(&[*d1, *d2])?;
+    let hour = number(&[*h1, *h2])?;
+    let minute = number(&[*mi1, *mi2])?;
+    let second = number(&[*s1, *s2])?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut rest = rest;
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let digits = fraction.iter().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+
+        // anything beyond nanosecond precision is truncated
+        for i in 0..9 {
+            nanos = nanos * 10 + fraction.get(i).filter(|_| i < digits).map_or(0, |c| u32::from(c - b'0'));
+        }
+
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest {
+        b"Z" | b"z" => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let hours = number(&[*h1, *h2]).filter(|h| *h <= 23)?;
+            let minutes = number(&[*m1, *m2]).filter(|m| *m <= 59)?;
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'-' { -offset } else { offset }
+        }
+        _ => return None,
+    };
+
+    let secs = days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second - offset;
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs.unsigned_abs()))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+    };
+
+    time?.checked_add(Duration::from_nanos(u64::from(nanos)))
+}
+
+/// Parse a fixed-width run of decimal digits.
+fn number(digits: &[u8]) -> Option<i64> {
+    digits
+        .iter()
+        .try_fold(0, |n, c| c.is_ascii_digit().then(|| n * 10 + i64::from(c - b'0')))
+}
+
+/// The number of days in the given month of the proleptic Gregorian calendar.
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Convert a number of days since the Unix epoch to a year, month, and day in the proleptic Gregorian calendar.
+const fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Convert a year, month, and day in the proleptic Gregorian calendar to a number of days since the Unix epoch.
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = (month + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_utc() {
+        assert_eq!(format(UNIX_EPOCH).unwrap(), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format(UNIX_EPOCH + Duration::new(1_709_210_096, 500_000_000)).unwrap(),
+            "2024-02-29T12:34:56.5Z"
+        );
+        assert_eq!(format(UNIX_EPOCH - Duration::from_millis(1)).unwrap(), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn rejects_years_beyond_rfc_3339() {
+        let last = parse("9999-12-31T23:59:59.999999999Z").unwrap();
+        assert_eq!(format(last).unwrap(), "9999-12-31T23:59:59.999999999Z");
+        assert_eq!(format(last + Duration::from_nanos(1)), None);
+
+        let first = parse("0000-01-01T00:00:00Z").unwrap();
+        assert_eq!(format(first).unwrap(), "0000-01-01T00:00:00Z");
+        assert_eq!(format(first - Duration::from_nanos(1)), None);
+    }
+
+    #[test]
+    fn parses_offsets_and_fractions() {
+        let expected = UNIX_EPOCH + Duration::new(1_709_210_096, 500_000_000);
+        assert_eq!(parse("2024-02-29T12:34:56.5Z"), Some(expected));
+        assert_eq!(parse("2024-02-29T14:34:56.500+02:00"), Some(expected));
+        assert_eq!(parse("2024-02-29t10:04:56.5000000001-02:30"), Some(expected));
+
+        assert_eq!(parse("2024-02-29T12:34:56"), None);
+        assert_eq!(parse("2024-13-01T00:00:00Z"), None);
+        assert_eq!(parse("2024-02-29T12:34:56.Z"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_offsets() {
+        assert_eq!(parse("2024-02-29T12:34:56+99:99"), None);
+        assert_eq!(parse("2024-02-29T12:34:56+24:00"), None);
+        assert_eq!(parse("2024-02-29T12:34:56-00:60"), None);
+
+        assert!(parse("2024-02-29T12:34:56+23:59").is_some());
+        assert!(parse("2024-02-29T12:34:56-00:00").is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert_eq!(parse("2025-02-29T00:00:00Z"), None);
+        assert_eq!(parse("2025-02-31T00:00:00Z"), None);
+        assert_eq!(parse("2025-04-31T00:00:00Z"), None);
+        assert_eq!(parse("1900-02-29T00:00:00Z"), None);
+        assert_eq!(parse("2025-01-00T00:00:00Z"), None);
+
+        assert!(parse("2000-02-29T00:00:00Z").is_some());
+        assert!(parse("2025-12-31T00:00:00Z").is_some());
+    }
+
+    #[test]
+    fn round_trips() {
+        for time in [UNIX_EPOCH, UNIX_EPOCH - Duration::new(86_400 * 400, 1), SystemTime::now()] {
+            assert_eq!(parse(&format(time).unwrap()), Some(time));
+        }
+    }
+}
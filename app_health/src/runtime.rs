@@ -0,0 +1,131 @@
+use core::fmt::Debug;
+use core::future::poll_fn;
+use core::pin::{Pin, pin};
+use core::task::Waker;
+use futures_util::{Stream, StreamExt, stream};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Runs the background workers of an aggregator and its components.
+///
+/// Aggregators, components, and health checks each run a small worker that processes their messages. The worker loops
+/// themselves only rely on executor-agnostic channels, so all they need from the surrounding runtime is a way to spawn
+/// them and a clock to drive debouncing, leases, and other timeouts. Tokio is only needed for
+/// [`TokioRuntime`](crate::TokioRuntime).
+///
+/// The crate provides three runtimes:
+///
+/// - [`TokioRuntime`](crate::TokioRuntime): Spawns workers as Tokio tasks. This is the default when the `tokio` feature is
+///   enabled, which it is by default.
+/// - [`ThreadRuntime`](crate::ThreadRuntime): Runs each worker on a dedicated thread, for applications without an async
+///   runtime. This requires the `threads` feature, and is the default when the `tokio` feature is disabled.
+/// - [`InlineRuntime`](crate::InlineRuntime): Runs workers on the caller's thread whenever the application calls into the
+///   crate, with no background tasks at all. This requires the `inline` feature, and is the default when it's the only
+///   runtime feature enabled.
+///
+/// Other executors, such as smol, can be supported by implementing this trait. Runtimes are configured with
+/// [`AggregatorBuilder::runtime`](crate::AggregatorBuilder::runtime), and all components of an aggregator share its runtime.
+pub trait Runtime: Debug + Send + Sync {
+    /// Run a future to completion in the background.
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>);
+
+    /// Get the current time.
+    fn now(&self) -> Instant;
+
+    /// Create a future that completes once the given deadline has passed.
+    ///
+    /// The future is only ever awaited from within futures passed to [`spawn`](Self::spawn).
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// Run the workers until none of them can make further progress.
+    ///
+    /// This is called after publishing a signal and before querying health, so that runtimes which don't run workers in
+    /// the background can catch up on pending work. Runtimes with background workers don't need to do anything, which
+    /// is the default.
+    fn run_until_stalled(&self) {}
+
+    /// Arrange for the given waker to be woken once one of the workers' timers runs out.
+    ///
+    /// This is called when a query has to wait for the workers, so that runtimes which don't run workers in the
+    /// background get to run them again once a timer is due. Runtimes with background workers don't need to do anything,
+    /// which is the default.
+    fn wake_when_due(&self, _waker: &Waker) {}
+
+    /// Returns `false` if updates should be published right away, regardless of the configured debounce intervals.
+    ///
+    /// Runtimes that only run workers when the application calls into the crate disable debouncing, so that every query
+    /// reflects everything published before it. Defaults to `true`.
+    fn supports_debouncing(&self) -> bool {
+        true
+    }
+}
+
+/// Wait for a future that depends on the workers, running them whenever the future is polled.
+pub async fn drive<F: Future + Send>(runtime: &dyn Runtime, future: F) -> F::Output {
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        runtime.run_until_stalled();
+        let poll = future.as_mut().poll(cx);
+        if poll.is_pending() {
+            runtime.wake_when_due(cx.waker());
+        }
+
+        poll
+    })
+    .await
+}
+
+/// Turn a stream that depends on the workers into one that runs them whenever it's polled.
+pub fn drive_stream<S: Stream + Send + 'static>(runtime: Arc<dyn Runtime>, stream: S) -> impl Stream<Item = S::Item> + Send + use<S> {
+    let mut stream = Box::pin(stream);
+    stream::poll_fn(move |cx| {
+        runtime.run_until_stalled();
+        let poll = stream.poll_next_unpin(cx);
+        if poll.is_pending() {
+            runtime.wake_when_due(cx.waker());
+        }
+
+        poll
+    })
+}
+
+/// Get the runtime used when none is configured explicitly.
+#[cfg(feature = "tokio")]
+pub fn default_runtime() -> Arc<dyn Runtime> {
+    Arc::new(crate::TokioRuntime::new())
+}
+
+/// Get the runtime used when none is configured explicitly.
+#[cfg(all(feature = "threads", not(feature = "tokio")))]
+pub fn default_runtime() -> Arc<dyn Runtime> {
+    Arc::new(crate::ThreadRuntime)
+}
+
+/// Get the runtime used when none is configured explicitly.
+#[cfg(all(feature = "inline", not(any(feature = "tokio", feature = "threads"))))]
+pub fn default_runtime() -> Arc<dyn Runtime> {
+    Arc::new(crate::InlineRuntime::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "tokio")]
+    fn defaults_to_tokio() {
+        assert!(format!("{:?}", default_runtime()).starts_with("TokioRuntime"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "threads", not(feature = "tokio")))]
+    fn defaults_to_threads_without_tokio() {
+        assert!(format!("{:?}", default_runtime()).starts_with("ThreadRuntime"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "inline", not(any(feature = "tokio", feature = "threads"))))]
+    fn defaults_to_inline_when_alone() {
+        assert!(format!("{:?}", default_runtime()).starts_with("InlineRuntime"));
+    }
+}
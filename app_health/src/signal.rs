@@ -3,6 +3,7 @@ use crate::Health;
 use core::fmt::Display;
 use core::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::SystemTime;
 
 /// A signal from a publisher that indicates its health with associated attributes.
 ///
@@ -12,11 +13,18 @@ use std::sync::Arc;
 ///
 /// Publisher signals are generated by functions such as [`nominal`](crate::Publisher::nominal) and
 /// [`degraded`](crate::Publisher::degraded) on a [`Publisher`](crate::Publisher).
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Signals also record when they were first published and when they were last reaffirmed by publishing them again.
+/// These timestamps don't take part in comparisons, so two signals are equal when their state and attributes are.
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Signal {
     state: Health,
     attributes: Arc<[Attribute]>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339"))]
+    first_published: SystemTime,
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339"))]
+    last_reaffirmed: SystemTime,
 }
 
 impl Signal {
@@ -25,19 +33,46 @@ impl Signal {
 
         v.sort_by(|x, y| x.name().cmp(y.name()));
 
+        let now = SystemTime::now();
         Self {
             state,
             attributes: Arc::from(v),
+            first_published: now,
+            last_reaffirmed: now,
         }
     }
 
     pub(crate) fn nominal() -> Self {
+        let now = SystemTime::now();
         Self {
             state: Health::Nominal,
             attributes: Arc::default(),
+            first_published: now,
+            last_reaffirmed: now,
+        }
+    }
+
+    /// A copy of this signal, as if it had just been published.
+    pub(crate) fn republished(&self) -> Self {
+        let now = SystemTime::now();
+        Self {
+            first_published: now,
+            last_reaffirmed: now,
+            ..self.clone()
         }
     }
 
+    /// Record that the signal was published again at the given time.
+    pub(crate) fn reaffirm(&mut self, at: SystemTime) {
+        self.last_reaffirmed = self.last_reaffirmed.max(at);
+    }
+
+    /// Combine the timestamps of an equal signal from another publisher into this one.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.first_published = self.first_published.min(other.first_published);
+        self.reaffirm(other.last_reaffirmed);
+    }
+
     /// The health state indicated by this signal.
     #[must_use]
     pub const fn state(&self) -> Health {
@@ -49,6 +84,29 @@ impl Signal {
     pub fn attributes(&self) -> &[Attribute] {
         &self.attributes
     }
+
+    /// When the signal was first published.
+    ///
+    /// In reports, this is when the earliest of the publishers reporting the signal started doing so.
+    #[must_use]
+    pub const fn first_published(&self) -> SystemTime {
+        self.first_published
+    }
+
+    /// When the signal was last published, either for the first time or by publishing it again unchanged.
+    ///
+    /// Heartbeats from publishers created with [`publisher_with_ttl`](crate::Component::publisher_with_ttl) also count.
+    /// In reports, this is the latest time any of the publishers reporting the signal reaffirmed it.
+    #[must_use]
+    pub const fn last_reaffirmed(&self) -> SystemTime {
+        self.last_reaffirmed
+    }
+}
+
+impl PartialEq for Signal {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state && self.attributes == other.attributes
+    }
 }
 
 impl Hash for Signal {
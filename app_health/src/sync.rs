@@ -0,0 +1,12 @@
+//! Executor-agnostic channels used by the workers.
+//!
+//! These mirror the parts of Tokio's `sync` module that the crate relies on, so that the workers can run on any
+//! [`Runtime`](crate::Runtime) without depending on Tokio.
+
+pub mod broadcast;
+pub mod mpsc;
+pub mod watch;
+
+pub use futures_channel::oneshot;
+
+mod wakers;
@@ -0,0 +1,163 @@
+use super::wakers::Wakers;
+use core::fmt::Debug;
+use core::future::poll_fn;
+use core::task::{Context, Poll};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The error returned when receiving from a channel fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvError {
+    /// Every sender has been dropped and every message has been received.
+    Closed,
+
+    /// The receiver fell behind, and the given number of its oldest messages were dropped.
+    Lagged(u64),
+}
+
+/// Create a channel that delivers each message to every receiver, keeping at most `capacity` messages around for
+/// receivers that haven't gotten to them yet.
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        messages: VecDeque::with_capacity(capacity),
+        capacity: capacity.max(1),
+        first: 0,
+        senders: 1,
+        receivers: 0,
+        wakers: Wakers::default(),
+    }));
+
+    let sender = Sender { shared };
+    let receiver = sender.subscribe();
+    (sender, receiver)
+}
+
+struct Shared<T> {
+    messages: VecDeque<T>,
+    capacity: usize,
+
+    // the sequence number of the oldest message kept around
+    first: u64,
+    senders: usize,
+    receivers: usize,
+    wakers: Wakers,
+}
+
+fn lock<T>(shared: &Mutex<Shared<T>>) -> MutexGuard<'_, Shared<T>> {
+    shared.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Sends messages to every receiver.
+pub struct Sender<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T: Clone> Sender<T> {
+    /// Send a message, getting it back if there are no receivers.
+    pub fn send(&self, msg: T) -> Result<(), T> {
+        let mut shared = lock(&self.shared);
+        if shared.receivers == 0 {
+            return Err(msg);
+        }
+
+        if shared.messages.len() == shared.capacity {
+            let _ = shared.messages.pop_front();
+            shared.first += 1;
+        }
+
+        shared.messages.push_back(msg);
+        shared.wakers.wake_all();
+        drop(shared);
+        Ok(())
+    }
+
+    /// Get a receiver for the messages sent from now on.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut shared = lock(&self.shared);
+        shared.receivers += 1;
+
+        Receiver {
+            next: shared.first + shared.messages.len() as u64,
+            key: shared.wakers.key(),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        lock(&self.shared).senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.senders -= 1;
+        if shared.senders == 0 {
+            shared.wakers.wake_all();
+        }
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// Receives every message sent after it subscribed.
+pub struct Receiver<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+
+    // the sequence number of the next message to receive
+    next: u64,
+    key: u64,
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Wait for the next message.
+    #[cfg_attr(not(feature = "otel"), expect(dead_code, reason = "only used by some features"))]
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    fn poll_recv(&mut self, cx: &Context<'_>) -> Poll<Result<T, RecvError>> {
+        let mut shared = lock(&self.shared);
+        if self.next < shared.first {
+            let lagged = shared.first - self.next;
+            self.next = shared.first;
+            return Poll::Ready(Err(RecvError::Lagged(lagged)));
+        }
+
+        let index = usize::try_from(self.next - shared.first).unwrap_or(usize::MAX);
+        if let Some(msg) = shared.messages.get(index) {
+            self.next += 1;
+            return Poll::Ready(Ok(msg.clone()));
+        }
+
+        if shared.senders == 0 {
+            return Poll::Ready(Err(RecvError::Closed));
+        }
+
+        shared.wakers.register(self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.receivers -= 1;
+        shared.wakers.remove(self.key);
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
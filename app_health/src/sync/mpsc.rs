@@ -0,0 +1,92 @@
+use core::fmt::Debug;
+use futures_channel::mpsc;
+use futures_util::StreamExt;
+use std::sync::{Arc, Weak};
+
+/// Create an unbounded channel with a single receiver.
+pub fn unbounded_channel<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    let (tx, rx) = mpsc::unbounded();
+    (UnboundedSender(Arc::new(tx)), UnboundedReceiver(rx))
+}
+
+/// Sends messages to the receiver, which keeps receiving for as long as any of these senders exist.
+pub struct UnboundedSender<T>(Arc<mpsc::UnboundedSender<T>>);
+
+impl<T> UnboundedSender<T> {
+    /// Send a message, getting it back if the receiver is gone.
+    pub fn send(&self, msg: T) -> Result<(), T> {
+        self.0.unbounded_send(msg).map_err(mpsc::TrySendError::into_inner)
+    }
+
+    /// Returns `true` once the receiver is gone.
+    #[cfg_attr(not(feature = "grpc"), expect(dead_code, reason = "only used by some features"))]
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    /// Get a sender that doesn't keep the receiver receiving.
+    pub fn downgrade(&self) -> WeakUnboundedSender<T> {
+        WeakUnboundedSender(Arc::downgrade(&self.0))
+    }
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+/// A sender that doesn't keep the receiver receiving, and must be upgraded to send.
+pub struct WeakUnboundedSender<T>(Weak<mpsc::UnboundedSender<T>>);
+
+impl<T> WeakUnboundedSender<T> {
+    /// Get a sender, unless every sender has been dropped already.
+    pub fn upgrade(&self) -> Option<UnboundedSender<T>> {
+        self.0.upgrade().map(UnboundedSender)
+    }
+
+    /// The number of senders that keep the receiver receiving.
+    pub fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+}
+
+impl<T> Clone for WeakUnboundedSender<T> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+/// Receives the messages of an unbounded channel.
+pub struct UnboundedReceiver<T>(mpsc::UnboundedReceiver<T>);
+
+impl<T> UnboundedReceiver<T> {
+    /// Wait for the next message, or `None` once every sender has been dropped and all messages have been received.
+    pub async fn recv(&mut self) -> Option<T> {
+        self.0.next().await
+    }
+
+    /// Get the next message if there is one already.
+    #[cfg(test)]
+    pub fn try_recv(&mut self) -> Result<T, mpsc::TryRecvError> {
+        self.0.try_recv()
+    }
+}
+
+impl<T> Debug for UnboundedSender<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UnboundedSender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Debug for WeakUnboundedSender<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WeakUnboundedSender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Debug for UnboundedReceiver<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UnboundedReceiver").finish_non_exhaustive()
+    }
+}
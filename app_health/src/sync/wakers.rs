@@ -0,0 +1,39 @@
+use core::task::Waker;
+
+/// The wakers of the receivers waiting on a channel, with at most one waker per receiver.
+///
+/// Receivers are identified by a key handed out by the channel, so that futures which are dropped without completing,
+/// as happens in `select!` loops, replace their stale wakers rather than piling them up.
+#[derive(Debug, Default)]
+pub struct Wakers {
+    entries: Vec<(u64, Waker)>,
+    next_key: u64,
+}
+
+impl Wakers {
+    /// Get a key for a new receiver.
+    pub const fn key(&mut self) -> u64 {
+        self.next_key += 1;
+        self.next_key
+    }
+
+    /// Register the waker of a receiver, replacing any it registered before.
+    pub fn register(&mut self, key: u64, waker: &Waker) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => existing.clone_from(waker),
+            None => self.entries.push((key, waker.clone())),
+        }
+    }
+
+    /// Forget the waker of a receiver that's going away.
+    pub fn remove(&mut self, key: u64) {
+        self.entries.retain(|(k, _)| *k != key);
+    }
+
+    /// Wake every waiting receiver.
+    pub fn wake_all(&mut self) {
+        for (_, waker) in self.entries.drain(..) {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use super::wakers::Wakers;
+use core::fmt::Debug;
+use core::future::poll_fn;
+use core::ops::Deref;
+use core::task::{Context, Poll};
+use futures_util::Stream;
+#[cfg(feature = "grpc")]
+use futures_util::StreamExt;
+use futures_util::stream;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard};
+
+/// A reference to the latest value of a channel, which holds up new values until it's dropped.
+pub struct Ref<'a, T> {
+    guard: RwLockReadGuard<'a, Versioned<T>>,
+}
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard.value
+    }
+}
+
+/// The error returned when waiting for a change after the sender has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+/// Create a channel that holds a single value, along with the sender that updates it.
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(Versioned { value: init, version: 0 }),
+        state: Mutex::new(State::default()),
+    });
+
+    let receiver = Receiver::new(Arc::clone(&shared), 0);
+    (Sender { shared }, receiver)
+}
+
+struct Shared<T> {
+    value: RwLock<Versioned<T>>,
+    state: Mutex<State>,
+}
+
+impl<T> Shared<T> {
+    fn read(&self) -> RwLockReadGuard<'_, Versioned<T>> {
+        self.value.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+struct Versioned<T> {
+    value: T,
+    version: u64,
+}
+
+#[derive(Default)]
+struct State {
+    wakers: Wakers,
+    closed: bool,
+}
+
+/// Updates the value of a channel, notifying every receiver.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Replace the value, notifying receivers even if it's equal to the previous one.
+    pub fn send(&self, value: T) {
+        let _ = self.send_if_modified(|current| {
+            *current = value;
+            true
+        });
+    }
+
+    /// Update the value in place, notifying receivers only if the update returns `true`.
+    pub fn send_if_modified(&self, modify: impl FnOnce(&mut T) -> bool) -> bool {
+        let mut current = self.shared.value.write().unwrap_or_else(PoisonError::into_inner);
+        if !modify(&mut current.value) {
+            return false;
+        }
+
+        current.version += 1;
+        drop(current);
+
+        self.shared.state().wakers.wake_all();
+        true
+    }
+
+    /// Get the latest value.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref { guard: self.shared.read() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state();
+        state.closed = true;
+        state.wakers.wake_all();
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// Observes the value of a channel, keeping track of which versions of it have been seen.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    seen: u64,
+    key: u64,
+}
+
+impl<T> Receiver<T> {
+    fn new(shared: Arc<Shared<T>>, seen: u64) -> Self {
+        let key = shared.state().wakers.key();
+        Self { shared, seen, key }
+    }
+
+    /// Get the latest value, without marking it as seen.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref { guard: self.shared.read() }
+    }
+
+    /// Get the latest value and mark it as seen.
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        let guard = self.shared.read();
+        self.seen = guard.version;
+        Ref { guard }
+    }
+
+    /// Wait for a value that hasn't been seen yet, and mark it as seen.
+    ///
+    /// Fails once the sender has been dropped and every value has been seen.
+    pub fn changed(&mut self) -> impl Future<Output = Result<(), RecvError>> + '_
+    where
+        T: Send + Sync,
+    {
+        poll_fn(|cx| self.poll_changed(cx))
+    }
+
+    fn poll_changed(&mut self, cx: &Context<'_>) -> Poll<Result<(), RecvError>> {
+        // the waker is registered before checking, so that a value sent in between isn't missed
+        let mut state = self.shared.state();
+        let version = self.shared.read().version;
+        if version != self.seen {
+            self.seen = version;
+            return Poll::Ready(Ok(()));
+        }
+
+        if state.closed {
+            return Poll::Ready(Err(RecvError));
+        }
+
+        state.wakers.register(self.key, cx.waker());
+        Poll::Pending
+    }
+
+    /// Wait for a value that satisfies the given condition, which may be the latest value even if it has been seen.
+    pub async fn wait_for(&mut self, mut condition: impl FnMut(&T) -> bool) -> Result<T, RecvError>
+    where
+        T: Clone + Send + Sync,
+    {
+        loop {
+            {
+                let current = self.borrow_and_update();
+                if condition(&current) {
+                    return Ok(current.clone());
+                }
+            }
+
+            self.changed().await?;
+        }
+    }
+
+    /// Turn the receiver into a stream of the values it hasn't seen yet.
+    ///
+    /// Values that are replaced before the stream gets to them are skipped.
+    pub fn into_changes(self) -> impl Stream<Item = T> + Send + use<T>
+    where
+        T: Clone + Send + Sync,
+    {
+        stream::unfold(self, |mut rx| async move {
+            rx.changed().await.ok()?;
+            let value = rx.borrow_and_update().clone();
+            Some((value, rx))
+        })
+    }
+
+    /// Turn the receiver into a stream that produces the latest value right away, followed by each later value.
+    #[cfg(feature = "grpc")]
+    pub fn into_stream(mut self) -> impl Stream<Item = T> + Send + use<T>
+    where
+        T: Clone + Send + Sync,
+    {
+        let current = self.borrow_and_update().clone();
+        stream::iter([current]).chain(self.into_changes())
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        Self::new(Arc::clone(&self.shared), self.seen)
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.state().wakers.remove(self.key);
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
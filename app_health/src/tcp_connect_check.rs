@@ -0,0 +1,120 @@
+use crate::health_check::{grade, saturating_i64};
+use crate::{Attribute, Health, HealthCheck};
+use core::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+
+/// Checks that a TCP connection can be established to a remote endpoint.
+///
+/// The check reports `Critical` if the connection fails. Otherwise, the time taken to connect is compared
+/// against the latency thresholds. The check produces the following attributes:
+///
+/// - `address`: The endpoint being checked.
+/// - `latency_ms`: The time taken to connect, when the connection succeeds.
+/// - `error`: The reason the connection failed, when it fails.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, TcpConnectCheck};
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let aggregator = Aggregator::new();
+///     let cache = aggregator.component("cache");
+///
+///     let check = TcpConnectCheck::new("localhost:6379").degraded_latency(Duration::from_millis(50));
+///     cache.health_check(check).start();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TcpConnectCheck {
+    address: Box<str>,
+    degraded_latency: Duration,
+    critical_latency: Duration,
+}
+
+impl TcpConnectCheck {
+    /// Create a check that connects to the given `host:port` address.
+    #[must_use]
+    pub fn new(address: impl AsRef<str>) -> Self {
+        Self {
+            address: address.as_ref().into(),
+            degraded_latency: Duration::from_millis(250),
+            critical_latency: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the connection latency at which the check reports `Degraded`.
+    ///
+    /// Defaults to 250 milliseconds.
+    #[must_use]
+    pub const fn degraded_latency(mut self, latency: Duration) -> Self {
+        self.degraded_latency = latency;
+        self
+    }
+
+    /// Set the connection latency at which the check reports `Critical`.
+    ///
+    /// Defaults to one second.
+    #[must_use]
+    pub const fn critical_latency(mut self, latency: Duration) -> Self {
+        self.critical_latency = latency;
+        self
+    }
+}
+
+impl HealthCheck for TcpConnectCheck {
+    async fn check(&self) -> (Health, Vec<Attribute>) {
+        let address = Attribute::from(("address", self.address.clone()));
+
+        let start = Instant::now();
+        match TcpStream::connect(&*self.address).await {
+            Ok(_) => {
+                let latency = start.elapsed();
+                (
+                    grade(latency, self.degraded_latency, self.critical_latency),
+                    vec![address, ("latency_ms", saturating_i64(latency.as_millis())).into()],
+                )
+            }
+
+            Err(e) => (Health::Critical, vec![address, ("error", e.to_string()).into()]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+        attributes.iter().find(|a| a.name().as_str() == name)
+    }
+
+    #[tokio::test]
+    async fn connects_to_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let check = TcpConnectCheck::new(listener.local_addr().unwrap().to_string());
+
+        let (state, attributes) = check.check().await;
+        assert_eq!(state, Health::Nominal);
+        assert!(attribute(&attributes, "latency_ms").is_some());
+
+        // any latency at all is too slow
+        let (state, _) = check.critical_latency(Duration::ZERO).check().await;
+        assert_eq!(state, Health::Critical);
+    }
+
+    #[tokio::test]
+    async fn reports_refused_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let (state, attributes) = TcpConnectCheck::new(address).check().await;
+        assert_eq!(state, Health::Critical);
+        assert!(attribute(&attributes, "error").is_some());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::Runtime;
+use core::cell::Cell;
+use core::pin::{Pin, pin};
+use core::task::{Context, Poll, Waker};
+use std::sync::Arc;
+use std::task::Wake;
+use std::thread::{self, Thread};
+use std::time::Instant;
+
+thread_local! {
+    // the earliest deadline of any sleep polled during the current poll of a worker
+    static NEXT_WAKEUP: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// A [`Runtime`] that runs each worker on a dedicated thread.
+///
+/// This runtime doesn't need an async executor, which makes it suitable for synchronous applications. Since every
+/// aggregator, component, and health check gets a thread of its own, it's best suited to applications with a modest
+/// number of components.
+///
+/// This runtime is available with the `threads` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Health, ThreadRuntime};
+///
+/// fn main() {
+///     let aggregator = Aggregator::builder().runtime(ThreadRuntime).build();
+///     let database = aggregator.component("database");
+///     let mut publisher = database.publisher();
+///
+///     publisher.publish(Health::Degraded, [("reason", "slow queries")]);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadRuntime;
+
+impl Runtime for ThreadRuntime {
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>) {
+        drop(thread::spawn(move || block_on(future)));
+    }
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(Sleep { deadline })
+    }
+}
+
+/// Run a future to completion on the current thread.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        NEXT_WAKEUP.set(None);
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        // sleeping futures don't have a waker to call, so we wake up by ourselves once the earliest one is due
+        match NEXT_WAKEUP.get() {
+            Some(deadline) => thread::park_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => thread::park(),
+        }
+    }
+}
+
+/// Wakes a worker by unparking its thread.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// A future that completes once a deadline has passed, when polled by [`block_on`].
+struct Sleep {
+    deadline: Instant,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        let deadline = NEXT_WAKEUP.get().map_or(self.deadline, |next| next.min(self.deadline));
+        NEXT_WAKEUP.set(Some(deadline));
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aggregator, Filter, Health};
+    use core::time::Duration;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    fn wait_for(mut condition: impl FnMut() -> bool) {
+        let deadline = Instant::now() + WAIT;
+        while !condition() {
+            assert!(Instant::now() < deadline, "timed out");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn sleeps_until_deadline() {
+        let start = Instant::now();
+        block_on(ThreadRuntime.sleep_until(start + Duration::from_millis(20)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn works_without_tokio() {
+        let aggregator = Aggregator::builder()
+            .runtime(ThreadRuntime)
+            .debounce_interval(Duration::from_millis(10))
+            .build();
+
+        let database = aggregator.component("database");
+        let mut publisher = database.publisher();
+        publisher.publish(Health::Critical, [("reason", "unreachable")]);
+
+        // the state goes through the debouncer, which needs the sleeps to work
+        wait_for(|| aggregator.state() == Health::Critical);
+
+        let reports: Vec<_> = block_on(aggregator.reports(Filter::ALL)).unwrap().collect();
+        assert_eq!(reports[0].signal_count(Health::Critical), 1);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Runtime;
+use core::pin::Pin;
+use std::time::Instant;
+use tokio::runtime::Handle;
+
+/// A [`Runtime`] that spawns workers as Tokio tasks.
+///
+/// By default, workers are spawned on the Tokio runtime that's current when they're created, so aggregators and components
+/// must be created from within a Tokio runtime. Use [`from_handle`](Self::from_handle) to spawn them on a specific runtime
+/// instead, which allows creating them from anywhere.
+///
+/// Time is measured with Tokio's clock, so pausing and advancing time in tests affects the workers as expected.
+///
+/// This runtime is available with the `tokio` feature.
+#[derive(Debug, Clone, Default)]
+pub struct TokioRuntime {
+    handle: Option<Handle>,
+}
+
+impl TokioRuntime {
+    /// Create a runtime that spawns workers on the current Tokio runtime.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { handle: None }
+    }
+
+    /// Create a runtime that spawns workers on the Tokio runtime with the given handle.
+    #[must_use]
+    pub const fn from_handle(handle: Handle) -> Self {
+        Self { handle: Some(handle) }
+    }
+}
+
+impl Runtime for TokioRuntime {
+    /// Spawn the future as a Tokio task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime wasn't created with a handle and this is called from outside of a Tokio runtime.
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>) {
+        match &self.handle {
+            Some(handle) => drop(handle.spawn(future)),
+            None => drop(tokio::spawn(future)),
+        }
+    }
+
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(tokio::time::sleep_until(deadline.into()))
+    }
+}
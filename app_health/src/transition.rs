@@ -0,0 +1,96 @@
+use crate::Health;
+use crate::signal::Signal;
+use crate::sync::watch;
+use core::fmt::Display;
+use core::future::ready;
+use core::mem::replace;
+use futures_util::{Stream, StreamExt};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// A change in the health state of an application or one of its components.
+///
+/// Each component keeps a bounded history of its most recent transitions, which can be retrieved with
+/// [`Component::history`](crate::Component::history) or [`Aggregator::history`](crate::Aggregator::history), or included in
+/// reports using [`Filter::HISTORY`](crate::Filter::HISTORY). Transitions can also be observed as they happen with
+/// [`Aggregator::watch`](crate::Aggregator::watch) and [`Component::watch`](crate::Component::watch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transition {
+    pub(crate) component: Option<Arc<str>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::rfc3339"))]
+    pub(crate) timestamp: SystemTime,
+    pub(crate) old_state: Health,
+    pub(crate) new_state: Health,
+    pub(crate) signal: Option<Signal>,
+}
+
+impl Transition {
+    /// The name of the component that changed state, or `None` for a change to the overall health of the application.
+    #[must_use]
+    pub fn component(&self) -> Option<&str> {
+        self.component.as_deref()
+    }
+
+    /// When the state changed.
+    #[must_use]
+    pub const fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// The state before the transition.
+    #[must_use]
+    pub const fn old_state(&self) -> Health {
+        self.old_state
+    }
+
+    /// The state after the transition.
+    #[must_use]
+    pub const fn new_state(&self) -> Health {
+        self.new_state
+    }
+
+    /// The most common signal in the new state at the time of the transition, which usually explains it.
+    ///
+    /// This is `None` when the component has no publishers in the new state, such as when its last publisher goes away.
+    /// It's also `None` for transitions observed through a stream, which only carry the states.
+    #[must_use]
+    pub const fn signal(&self) -> Option<&Signal> {
+        self.signal.as_ref()
+    }
+}
+
+impl Display for Transition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let component = self.component.as_deref().unwrap_or("application");
+        write!(f, "{component}: {} -> {}", self.old_state, self.new_state)?;
+        if let Some(signal) = &self.signal {
+            write!(f, " ({signal})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Turn a receiver of health updates into a stream of the transitions between distinct states.
+pub fn transitions<T: Clone + Send + Sync + 'static>(
+    mut rx: watch::Receiver<T>,
+    component: Option<Arc<str>>,
+    state: fn(&T) -> Health,
+) -> impl Stream<Item = Transition> + Send + use<T> {
+    let mut last = state(&rx.borrow_and_update());
+
+    // updates don't necessarily change the state, so those are skipped
+    rx.into_changes().filter_map(move |value| {
+        let new_state = state(&value);
+        let old_state = replace(&mut last, new_state);
+
+        ready((old_state != new_state).then(|| Transition {
+            component: component.clone(),
+            timestamp: SystemTime::now(),
+            old_state,
+            new_state,
+            signal: None,
+        }))
+    })
+}
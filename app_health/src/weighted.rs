@@ -0,0 +1,142 @@
+use crate::health::ALL_HEALTH_STATES;
+use crate::{AggregationPolicy, Health, Report};
+use std::collections::HashMap;
+
+/// An aggregation policy where each component carries a weight.
+///
+/// The overall health is the most severe state `S` such that the components in state `S` or worse
+/// account for at least `threshold` of the total weight of all components. For example, with a threshold of `0.5`,
+/// the application is [`Down`](Health::Down) only once components holding half of the total weight are down.
+///
+/// Each component's health is capped according to its [`Criticality`](crate::Criticality).
+/// Components have a weight of `1.0` unless configured otherwise with [`weight`](Self::weight).
+///
+/// # Example
+///
+/// ```rust
+/// use app_health::{Aggregator, Weighted};
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let policy = Weighted::new(0.5).weight("database", 4.0).weight("analytics", 0.5);
+///     let aggregator = Aggregator::builder().policy(policy).build();
+///     let _database = aggregator.component("database");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Weighted {
+    threshold: f64,
+    default_weight: f64,
+    weights: HashMap<Box<str>, f64>,
+}
+
+impl Weighted {
+    /// Create a policy with the given threshold, expressed as a fraction of the total component weight.
+    #[must_use]
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            default_weight: 1.0,
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Set the weight of the component with the given name.
+    #[must_use]
+    pub fn weight(mut self, component: impl AsRef<str>, weight: f64) -> Self {
+        let _ = self.weights.insert(component.as_ref().into(), weight);
+        self
+    }
+
+    /// Set the weight of components that don't have an explicit weight.
+    #[must_use]
+    pub const fn default_weight(mut self, weight: f64) -> Self {
+        self.default_weight = weight;
+        self
+    }
+
+    fn weight_of(&self, component: &Report) -> f64 {
+        self.weights.get(component.name()).copied().unwrap_or(self.default_weight)
+    }
+}
+
+impl AggregationPolicy for Weighted {
+    fn aggregate(&self, components: &[Report]) -> Health {
+        let total: f64 = components.iter().map(|c| self.weight_of(c)).sum();
+        if total <= 0.0 {
+            return Health::Nominal;
+        }
+
+        let mut accumulated = 0.0;
+        for state in ALL_HEALTH_STATES.iter().rev() {
+            accumulated += components
+                .iter()
+                .filter(|c| c.effective_state() == *state)
+                .map(|c| self.weight_of(c))
+                .sum::<f64>();
+
+            if accumulated / total >= self.threshold {
+                return *state;
+            }
+        }
+
+        Health::Nominal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(name: &str, state: Health) -> Report {
+        Report {
+            name: name.into(),
+            state,
+            ..Report::default()
+        }
+    }
+
+    #[test]
+    fn heavy_component_dominates() {
+        let policy = Weighted::new(0.5).weight("database", 3.0);
+        let components = [
+            report("database", Health::Down),
+            report("cache", Health::Nominal),
+            report("analytics", Health::Degraded),
+        ];
+
+        assert_eq!(policy.aggregate(&components), Health::Down);
+    }
+
+    #[test]
+    fn light_components_are_outvoted() {
+        let policy = Weighted::new(0.5).weight("database", 3.0);
+        let components = [
+            report("database", Health::Nominal),
+            report("cache", Health::Down),
+            report("analytics", Health::Degraded),
+        ];
+
+        assert_eq!(policy.aggregate(&components), Health::Nominal);
+    }
+
+    #[test]
+    fn severities_accumulate() {
+        let policy = Weighted::new(0.5);
+        let components = [
+            report("a", Health::Down),
+            report("b", Health::Critical),
+            report("c", Health::Nominal),
+            report("d", Health::Nominal),
+        ];
+
+        assert_eq!(policy.aggregate(&components), Health::Critical);
+    }
+
+    #[test]
+    fn no_weight_is_nominal() {
+        let policy = Weighted::new(0.5).default_weight(0.0);
+        assert_eq!(policy.aggregate(&[report("a", Health::Down)]), Health::Nominal);
+        assert_eq!(policy.aggregate(&[]), Health::Nominal);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{AggregationPolicy, Health, Report};
+
+/// An aggregation policy where the overall health is the most severe health of any component.
+///
+/// Each component's health is capped according to its [`Criticality`](crate::Criticality).
+/// This is the default policy used by an aggregator. An application without any components is
+/// considered [`Nominal`](Health::Nominal).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorstOf;
+
+impl AggregationPolicy for WorstOf {
+    fn aggregate(&self, components: &[Report]) -> Health {
+        components.iter().map(Report::effective_state).max().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(state: Health) -> Report {
+        Report {
+            state,
+            ..Report::default()
+        }
+    }
+
+    #[test]
+    fn empty_is_nominal() {
+        assert_eq!(WorstOf.aggregate(&[]), Health::Nominal);
+    }
+
+    #[test]
+    fn respects_criticality() {
+        let analytics = Report {
+            state: Health::Down,
+            criticality: crate::Criticality::Optional(Health::Degraded),
+            ..Report::default()
+        };
+
+        assert_eq!(WorstOf.aggregate(&[analytics, report(Health::Nominal)]), Health::Degraded);
+    }
+
+    #[test]
+    fn picks_most_severe() {
+        let components = [report(Health::Degraded), report(Health::Down), report(Health::Nominal)];
+        assert_eq!(WorstOf.aggregate(&components), Health::Down);
+    }
+}